@@ -0,0 +1,30 @@
+/// Defines how locations outside of an image are treated by neighborhood operations.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BorderMode {
+    /// Use the closest pixel inside of the image.
+    Clamp,
+    /// Treat every pixel outside of the image as zero.
+    Zero,
+    /// Continue with the pixels from the opposite side of the image.
+    Wrap,
+}
+
+impl BorderMode {
+    /// Maps a possibly out of bounds coordinate into the range `0..len`.
+    ///
+    /// Returns `None` if the location does not correspond to any pixel, which is the case for
+    /// out of bounds coordinates with `BorderMode::Zero` and for empty images.
+    pub(crate) fn resolve(self, coord: i64, len: u32) -> Option<u32> {
+        if len == 0 {
+            return None;
+        }
+        if coord >= 0 && coord < len as i64 {
+            return Some(coord as u32);
+        }
+        match self {
+            BorderMode::Clamp => Some(if coord < 0 { 0 } else { len - 1 }),
+            BorderMode::Zero => None,
+            BorderMode::Wrap => Some(coord.rem_euclid(len as i64) as u32),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::f64::consts::PI;
+
+/// Complex number as (real, imaginary) part.
+pub(crate) type Complex64 = (f64, f64);
+
+/// Computes the discrete Fourier transform of `data` in place.
+///
+/// The forward transform is unnormalized, the inverse transform is scaled by `1 / n`, so both
+/// together are the identity. Power-of-two lengths use a radix-2 FFT, other lengths fall back
+/// to the `O(n^2)` definition.
+pub(crate) fn fft(data: &mut [Complex64], inverse: bool) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    if n.is_power_of_two() {
+        // bit reversal permutation
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                data.swap(i, j);
+            }
+        }
+        let mut len = 2;
+        while len <= n {
+            let angle = sign * 2.0 * PI / len as f64;
+            for start in (0..n).step_by(len) {
+                for k in 0..len / 2 {
+                    let (sin, cos) = (angle * k as f64).sin_cos();
+                    let (ar, ai) = data[start + k];
+                    let (br, bi) = data[start + k + len / 2];
+                    let (tr, ti) = (br * cos - bi * sin, br * sin + bi * cos);
+                    data[start + k] = (ar + tr, ai + ti);
+                    data[start + k + len / 2] = (ar - tr, ai - ti);
+                }
+            }
+            len <<= 1;
+        }
+    } else {
+        let input = data.to_vec();
+        for (k, out) in data.iter_mut().enumerate() {
+            *out = input.iter().enumerate().fold((0.0, 0.0), |(sr, si), (t, &(r, i))| {
+                let angle = sign * 2.0 * PI * ((k * t) % n) as f64 / n as f64;
+                let (sin, cos) = angle.sin_cos();
+                (sr + r * cos - i * sin, si + r * sin + i * cos)
+            });
+        }
+    }
+    if inverse {
+        for value in data.iter_mut() {
+            *value = (value.0 / n as f64, value.1 / n as f64);
+        }
+    }
+}
+
+/// Computes the 2D discrete Fourier transform of the row-major `data` in place, as 1D
+/// transforms along all rows followed by all columns. See [`fft`](fn.fft.html).
+pub(crate) fn fft2(data: &mut [Complex64], width: u32, height: u32, inverse: bool) {
+    let (width, height) = (width as usize, height as usize);
+    assert_eq!(data.len(), width * height);
+    for row in data.chunks_mut(width.max(1)) {
+        fft(row, inverse);
+    }
+    let mut column = vec![(0.0, 0.0); height];
+    for x in 0..width {
+        for (y, value) in column.iter_mut().enumerate() {
+            *value = data[y * width + x];
+        }
+        fft(&mut column, inverse);
+        for (y, value) in column.iter().enumerate() {
+            data[y * width + x] = *value;
+        }
+    }
+}
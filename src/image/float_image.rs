@@ -0,0 +1,282 @@
+use {Scalar, PixelArithmetic};
+use {Image, ImageVal, ImageBufferVal, BorderMode};
+
+/// Channel-wise `f64` copy of an image.
+///
+/// This is the intermediate representation for operations which need more precision or range
+/// than the scalar type of the image provides, e.g. filters which subtract or accumulate.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FloatImage {
+    width: u32,
+    height: u32,
+    channels: usize,
+    data: Vec<f64>,
+}
+
+impl FloatImage {
+    /// Constructs a zeroed image.
+    pub(crate) fn new(width: u32, height: u32, channels: usize) -> FloatImage {
+        FloatImage {
+            width,
+            height,
+            channels,
+            data: vec![0.0; (width as usize) * (height as usize) * channels],
+        }
+    }
+
+    /// Converts every channel of `image` into `f64`.
+    pub(crate) fn from_image<ImageP>(image: &ImageVal<ImageP>) -> FloatImage
+        where ImageP: Image,
+              ImageP::PixelT: PixelArithmetic
+    {
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let mut result = FloatImage::new(image.width(), image.height(), channels);
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                let pixel = image.get_pixel(x, y).unwrap();
+                for idx in 0..channels {
+                    result.set(x, y, idx, pixel.0.get_channel(idx).to_f64());
+                }
+            }
+        }
+        result
+    }
+
+    /// Converts back into an image buffer, see
+    /// [`Scalar::from_f64`](trait.Scalar.html#tymethod.from_f64) for the rounding behavior.
+    pub(crate) fn to_image<PixelP>(&self) -> ImageBufferVal<PixelP>
+        where PixelP: PixelArithmetic
+    {
+        assert_eq!(self.channels, PixelP::CHANNEL_COUNT);
+        let mut result = ImageBufferVal::<PixelP>::new_with_size(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut pixel = result.get_pixel(x, y).unwrap();
+                for idx in 0..self.channels {
+                    pixel.0.set_channel(idx, Scalar::from_f64(self.get(x, y, idx)));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+
+    pub(crate) fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub(crate) fn get(&self, x: u32, y: u32, channel: usize) -> f64 {
+        self.data[self.index(x, y, channel)]
+    }
+
+    pub(crate) fn set(&mut self, x: u32, y: u32, channel: usize, value: f64) {
+        let idx = self.index(x, y, channel);
+        self.data[idx] = value;
+    }
+
+    /// Combines every value with the value at the same position in `other`.
+    pub(crate) fn zip_with<F>(&self, other: &FloatImage, f: F) -> FloatImage
+        where F: Fn(f64, f64) -> f64
+    {
+        assert_eq!(self.width, other.width);
+        assert_eq!(self.height, other.height);
+        assert_eq!(self.channels, other.channels);
+        FloatImage {
+            data: self.data.iter().zip(other.data.iter()).map(|(&a, &b)| f(a, b)).collect(),
+            ..*self
+        }
+    }
+
+    /// Mixes with `other` according to the single channel `weights`, computing
+    /// `weight * self + (1 - weight) * other` for every channel.
+    pub(crate) fn blend(&self, other: &FloatImage, weights: &FloatImage) -> FloatImage {
+        assert_eq!(weights.channels, 1);
+        assert_eq!((weights.width, weights.height), (self.width, self.height));
+        let mut result = self.zip_with(other, |a, b| a - b);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let weight = weights.get(x, y, 0);
+                for c in 0..self.channels {
+                    let value = other.get(x, y, c) + weight * result.get(x, y, c);
+                    result.set(x, y, c, value);
+                }
+            }
+        }
+        result
+    }
+
+    /// Applies a separable Gaussian blur with standard deviation `sigma`.
+    ///
+    /// The kernel is truncated at a radius of `ceil(3 * sigma)` and renormalized, locations
+    /// outside of the image are clamped.
+    pub(crate) fn gaussian_blur(&self, sigma: f64) -> FloatImage {
+        if sigma == 0.0 {
+            return self.clone();
+        }
+        let radius = (3.0 * sigma).ceil() as i64;
+        let mut weights: Vec<f64> = (-radius..radius + 1)
+            .map(|d| (-((d * d) as f64) / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        for weight in weights.iter_mut() {
+            *weight /= total;
+        }
+        self.convolve_separable(&weights, &weights)
+    }
+
+    /// Convolves with a separable kernel given by its horizontal and vertical part.
+    ///
+    /// Both parts need an odd length, locations outside of the image are clamped.
+    pub(crate) fn convolve_separable(&self, horizontal: &[f64], vertical: &[f64]) -> FloatImage {
+        let horizontal_radius = (horizontal.len() / 2) as i64;
+        let vertical_radius = (vertical.len() / 2) as i64;
+        let mut temp = FloatImage::new(self.width, self.height, self.channels);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                for c in 0..self.channels {
+                    let mut sum = 0.0;
+                    for (i, weight) in horizontal.iter().enumerate() {
+                        let sx = BorderMode::Clamp
+                            .resolve(x as i64 + i as i64 - horizontal_radius, self.width)
+                            .unwrap();
+                        sum += weight * self.get(sx, y, c);
+                    }
+                    temp.set(x, y, c, sum);
+                }
+            }
+        }
+        let mut result = FloatImage::new(self.width, self.height, self.channels);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                for c in 0..self.channels {
+                    let mut sum = 0.0;
+                    for (i, weight) in vertical.iter().enumerate() {
+                        let sy = BorderMode::Clamp
+                            .resolve(y as i64 + i as i64 - vertical_radius, self.height)
+                            .unwrap();
+                        sum += weight * temp.get(x, sy, c);
+                    }
+                    result.set(x, y, c, sum);
+                }
+            }
+        }
+        result
+    }
+
+    /// Blurs with a Gaussian of `sigma = 1` and drops every other row and column.
+    ///
+    /// The result has a size of `ceil(width / 2) x ceil(height / 2)`, it is one level of a
+    /// Gaussian pyramid.
+    pub(crate) fn pyramid_down(&self) -> FloatImage {
+        let blurred = self.gaussian_blur(1.0);
+        let mut result =
+            FloatImage::new(self.width.div_ceil(2), self.height.div_ceil(2), self.channels);
+        for y in 0..result.height {
+            for x in 0..result.width {
+                for c in 0..self.channels {
+                    result.set(x, y, c, blurred.get(2 * x, 2 * y, c));
+                }
+            }
+        }
+        result
+    }
+
+    /// Enlarges to `width x height` with bilinear interpolation, the counterpart of
+    /// [`pyramid_down`](#method.pyramid_down).
+    pub(crate) fn pyramid_up(&self, width: u32, height: u32) -> FloatImage {
+        self.resample(width, height, 1.0, |d| (1.0 - d.abs()).max(0.0))
+    }
+
+    /// Samples `channel` at the sub-pixel location (`x`, `y`) with bilinear interpolation.
+    ///
+    /// Pixel centers are located at integer coordinates. Neighbors outside of the image are
+    /// handled according to `border`. Non-finite coordinates are outside of the image as well,
+    /// positive infinity is treated like the location just behind the last pixel and negative
+    /// infinity or NaN like the location just before the first pixel.
+    pub(crate) fn sample_bilinear(&self,
+                                  x: f64,
+                                  y: f64,
+                                  channel: usize,
+                                  border: BorderMode)
+                                  -> f64 {
+        let outside = |coord: f64, len: u32| if coord == f64::INFINITY { len as f64 } else { -1.0 };
+        let x = if x.is_finite() { x } else { outside(x, self.width) };
+        let y = if y.is_finite() { y } else { outside(y, self.height) };
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let mut result = 0.0;
+        for &(dx, dy, weight) in &[(0, 0, (1.0 - fx) * (1.0 - fy)),
+                                   (1, 0, fx * (1.0 - fy)),
+                                   (0, 1, (1.0 - fx) * fy),
+                                   (1, 1, fx * fy)] {
+            if weight == 0.0 {
+                continue;
+            }
+            let sx = border.resolve((x0 as i64).saturating_add(dx), self.width);
+            let sy = border.resolve((y0 as i64).saturating_add(dy), self.height);
+            if let (Some(sx), Some(sy)) = (sx, sy) {
+                result += weight * self.get(sx, sy, channel);
+            }
+        }
+        result
+    }
+
+    /// Resamples to `new_width` x `new_height` with a separable filter.
+    ///
+    /// `weight` gives the filter weight for a distance in source pixels and is evaluated for
+    /// every source pixel closer than `support`. Pixel centers are aligned, the weights are
+    /// renormalized and locations outside of the image are clamped.
+    pub(crate) fn resample<F>(&self,
+                              new_width: u32,
+                              new_height: u32,
+                              support: f64,
+                              weight: F)
+                              -> FloatImage
+        where F: Fn(f64) -> f64
+    {
+        let taps = |dst: u32, src_len: u32, dst_len: u32| -> Vec<(u32, f64)> {
+            let center = (dst as f64 + 0.5) * (src_len as f64) / (dst_len as f64) - 0.5;
+            let first = (center - support).floor() as i64;
+            let last = (center + support).ceil() as i64;
+            let mut taps: Vec<(u32, f64)> = (first..last + 1)
+                .map(|s| (s, weight(s as f64 - center)))
+                .filter(|&(_, w)| w != 0.0)
+                .map(|(s, w)| (BorderMode::Clamp.resolve(s, src_len).unwrap(), w))
+                .collect();
+            let total: f64 = taps.iter().map(|&(_, w)| w).sum();
+            for tap in taps.iter_mut() {
+                tap.1 /= total;
+            }
+            taps
+        };
+
+        let mut temp = FloatImage::new(new_width, self.height, self.channels);
+        for x in 0..new_width {
+            let taps = taps(x, self.width, new_width);
+            for y in 0..self.height {
+                for c in 0..self.channels {
+                    let sum = taps.iter().map(|&(sx, w)| w * self.get(sx, y, c)).sum();
+                    temp.set(x, y, c, sum);
+                }
+            }
+        }
+        let mut result = FloatImage::new(new_width, new_height, self.channels);
+        for y in 0..new_height {
+            let taps = taps(y, self.height, new_height);
+            for x in 0..new_width {
+                for c in 0..self.channels {
+                    let sum = taps.iter().map(|&(sy, w)| w * temp.get(x, sy, c)).sum();
+                    result.set(x, y, c, sum);
+                }
+            }
+        }
+        result
+    }
+
+    fn index(&self, x: u32, y: u32, channel: usize) -> usize {
+        assert!(x < self.width && y < self.height && channel < self.channels);
+        ((y as usize) * (self.width as usize) + (x as usize)) * self.channels + channel
+    }
+}
@@ -0,0 +1,96 @@
+use PixelVal;
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Crops the centered region with the aspect ratio `aspect_w : aspect_h`.
+    ///
+    /// The result is as large as possible: it keeps the full height of an image which is too
+    /// wide and the full width of an image which is too tall. The other dimension is rounded
+    /// to the nearest integer, but at least one pixel. If the crop can't be centered exactly,
+    /// the extra pixel is removed from the right or bottom. An image which already has the
+    /// target ratio is returned unchanged.
+    ///
+    /// # Panics
+    /// If `aspect_w` or `aspect_h` is zero.
+    pub fn crop_to_aspect(&self, aspect_w: u32, aspect_h: u32) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(aspect_w > 0 && aspect_h > 0,
+                "Invalid aspect ratio ({:}:{:})",
+                aspect_w,
+                aspect_h);
+
+        let (width, height) = (self.width(), self.height());
+        let (new_width, new_height) = if is_wider(width, height, aspect_w, aspect_h) {
+            (scale(height, aspect_w, aspect_h).max(1).min(width), height)
+        } else {
+            (width, scale(width, aspect_h, aspect_w).max(1).min(height))
+        };
+        let offset_x = (width - new_width) / 2;
+        let offset_y = (height - new_height) / 2;
+
+        let mut result = ImageBufferVal::new_with_size(new_width, new_height);
+        for y in 0..new_height {
+            for x in 0..new_width {
+                result.set_pixel(x, y, self.get_pixel(x + offset_x, y + offset_y).unwrap());
+            }
+        }
+        result
+    }
+
+    /// Extends the image to the aspect ratio `aspect_w : aspect_h` with bars of `fill`.
+    ///
+    /// An image which is too wide gets bars at the top and bottom (letterbox), an image which
+    /// is too tall at the left and right (pillarbox). The original image is centered and kept
+    /// at its full size, the new dimension is rounded to the nearest integer. If the bars can't
+    /// be of equal size, the extra pixel goes to the right or bottom bar. An image which
+    /// already has the target ratio is returned unchanged.
+    ///
+    /// # Panics
+    /// If `aspect_w` or `aspect_h` is zero.
+    pub fn pad_to_aspect(&self,
+                         aspect_w: u32,
+                         aspect_h: u32,
+                         fill: PixelVal<ImageP::PixelT>)
+                         -> ImageBufferVal<ImageP::PixelT> {
+        assert!(aspect_w > 0 && aspect_h > 0,
+                "Invalid aspect ratio ({:}:{:})",
+                aspect_w,
+                aspect_h);
+
+        let (width, height) = (self.width(), self.height());
+        let (new_width, new_height) = if is_wider(width, height, aspect_w, aspect_h) {
+            (width, scale(width, aspect_h, aspect_w).max(height))
+        } else {
+            (scale(height, aspect_w, aspect_h).max(width), height)
+        };
+        let offset_x = (new_width - width) / 2;
+        let offset_y = (new_height - height) / 2;
+
+        let mut result = ImageBufferVal::new_with_size(new_width, new_height);
+        for y in 0..new_height {
+            for x in 0..new_width {
+                let inside = (offset_x..offset_x + width).contains(&x) &&
+                             (offset_y..offset_y + height).contains(&y);
+                let pixel = if inside {
+                    self.get_pixel(x - offset_x, y - offset_y).unwrap()
+                } else {
+                    fill
+                };
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+}
+
+/// Returns whether `width : height` is wider than `aspect_w : aspect_h`.
+fn is_wider(width: u32, height: u32, aspect_w: u32, aspect_h: u32) -> bool {
+    (width as u64) * (aspect_h as u64) > (height as u64) * (aspect_w as u64)
+}
+
+/// Computes `value * numerator / denominator` rounded to the nearest integer.
+fn scale(value: u32, numerator: u32, denominator: u32) -> u32 {
+    let denominator = denominator as u64;
+    (((value as u64) * (numerator as u64) + denominator / 2) / denominator) as u32
+}
@@ -0,0 +1,76 @@
+use {Scalar, ScalarVal, Gray, Rgb, RgbVal};
+use {Image, ImageVal, ImageBufferVal};
+
+/// Layout of the color filter array of a raw sensor image, used by
+/// [`ImageVal::demosaic`](struct.ImageVal.html#method.demosaic).
+///
+/// The name lists the colors of the 2x2 tile at the image origin in row-major order, e.g.
+/// `Rggb` has red at (0, 0), green at (1, 0) and (0, 1) and blue at (1, 1).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BayerPattern {
+    /// Red, green / green, blue.
+    Rggb,
+    /// Blue, green / green, red.
+    Bggr,
+    /// Green, red / blue, green.
+    Grbg,
+    /// Green, blue / red, green.
+    Gbrg,
+}
+
+impl BayerPattern {
+    /// Channel index (0 = red, 1 = green, 2 = blue) of the filter at (`x`, `y`).
+    fn channel_at(self, x: u32, y: u32) -> usize {
+        let tile = match self {
+            BayerPattern::Rggb => [0, 1, 1, 2],
+            BayerPattern::Bggr => [2, 1, 1, 0],
+            BayerPattern::Grbg => [1, 0, 2, 1],
+            BayerPattern::Gbrg => [1, 2, 0, 1],
+        };
+        tile[((y % 2) * 2 + x % 2) as usize]
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Reconstructs a color image from raw sensor data with bilinear demosaicing.
+    ///
+    /// Every pixel holds the value of a single color, which one is given by `pattern`
+    /// relative to the image origin. The measured color is kept and the two missing colors
+    /// are the mean of the pixels of that color in the 3x3 neighborhood, i.e. of the two or
+    /// four closest ones. At the border only the neighbors inside of the image are averaged.
+    pub fn demosaic(&self, pattern: BayerPattern) -> ImageBufferVal<Rgb<ScalarP>> {
+        let width = self.width();
+        let height = self.height();
+        let value = |x: u32, y: u32| self.get_pixel(x, y).unwrap().intensity().0.to_f64();
+
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sums = [0f64; 3];
+                let mut counts = [0u32; 3];
+                for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                    for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                        let channel = pattern.channel_at(nx, ny);
+                        sums[channel] += value(nx, ny);
+                        counts[channel] += 1;
+                    }
+                }
+                let own = pattern.channel_at(x, y);
+                sums[own] = value(x, y);
+                counts[own] = 1;
+
+                // an image smaller than 2x2 doesn't contain all colors
+                let channel = |c: usize| if counts[c] == 0 {
+                    ScalarVal(ScalarP::range_min())
+                } else {
+                    ScalarVal(Scalar::from_f64(sums[c] / counts[c] as f64))
+                };
+                result.set_pixel(x, y, RgbVal::new(channel(0), channel(1), channel(2)));
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,68 @@
+use {PixelArithmetic, Gray8U};
+use {Image, ImageVal, ImageBufferVal};
+use image::float_image::FloatImage;
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Composites this image and `other` with Laplacian pyramid blending.
+    ///
+    /// `mask` selects the source per pixel: `255` takes this image, `0` takes `other` and
+    /// values in between mix both. Instead of mixing the pixels directly, both images are
+    /// decomposed into Laplacian pyramids with `levels` levels below the full resolution
+    /// (fewer if a level shrinks to a single pixel), every level is blended with the
+    /// corresponding level of a Gaussian pyramid of the mask and the result is collapsed
+    /// again. Coarse structures are thus blended over a wide region and fine details over a
+    /// narrow one, which hides the seam of a hard mask. With `levels = 0` this is a plain
+    /// mask blend.
+    ///
+    /// # Panics
+    /// If the sizes of the images and the mask differ.
+    pub fn pyramid_blend<ImageB, ImageM>(&self,
+                                         other: &ImageVal<ImageB>,
+                                         mask: &ImageVal<ImageM>,
+                                         levels: usize)
+                                         -> ImageBufferVal<ImageP::PixelT>
+        where ImageB: Image<PixelT = ImageP::PixelT>,
+              ImageM: Image<PixelT = Gray8U>
+    {
+        let size = (self.width(), self.height());
+        assert!(size == (other.width(), other.height()) && size == (mask.width(), mask.height()),
+                "Sizes of the images and the mask differ");
+
+        let mut weights = FloatImage::new(size.0, size.1, 1);
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let weight = mask.get_pixel(x, y).unwrap().intensity().0 as f64 / 255.0;
+                weights.set(x, y, 0, weight);
+            }
+        }
+
+        let mut gaussian_a = vec![FloatImage::from_image(self)];
+        let mut gaussian_b = vec![FloatImage::from_image(other)];
+        let mut gaussian_mask = vec![weights];
+        while gaussian_a.len() <= levels && gaussian_a.last().unwrap().size() != (1, 1) {
+            let next_a = gaussian_a.last().unwrap().pyramid_down();
+            let next_b = gaussian_b.last().unwrap().pyramid_down();
+            let next_mask = gaussian_mask.last().unwrap().pyramid_down();
+            gaussian_a.push(next_a);
+            gaussian_b.push(next_b);
+            gaussian_mask.push(next_mask);
+        }
+
+        // blend the coarsest level directly, then add the blended details of the finer levels
+        let top = gaussian_a.len() - 1;
+        let mut result = gaussian_a[top].blend(&gaussian_b[top], &gaussian_mask[top]);
+        for level in (0..top).rev() {
+            let (width, height) = gaussian_a[level].size();
+            let details = |gaussian: &[FloatImage]| {
+                gaussian[level].zip_with(&gaussian[level + 1].pyramid_up(width, height),
+                                         |fine, coarse| fine - coarse)
+            };
+            let blended = details(&gaussian_a).blend(&details(&gaussian_b), &gaussian_mask[level]);
+            result = result.pyramid_up(width, height).zip_with(&blended, |a, b| a + b);
+        }
+        result.to_image()
+    }
+}
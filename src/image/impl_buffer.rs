@@ -1,6 +1,7 @@
 use std::vec::Vec;
 use std::marker::PhantomData;
-use {Pixel, Image, ImageVal};
+use std::ops::{Deref, DerefMut, Range};
+use {Pixel, PixelVal, Image, ImageVal};
 
 /// Defines a buffer object which can store image data.
 ///
@@ -20,7 +21,7 @@ pub struct ImageBuffer<PixelP>
 }
 
 /// Convenient abbreviation
-pub type ImageBufferVal<PixelP> where PixelP: Pixel = ImageVal<ImageBuffer<PixelP>>;
+pub type ImageBufferVal<PixelP> = ImageVal<ImageBuffer<PixelP>>;
 
 impl<PixelP> ImageBufferVal<PixelP>
     where PixelP: Pixel
@@ -42,21 +43,246 @@ impl<PixelP> ImageBufferVal<PixelP>
     /// depend on the [`Pixel`](trait.Pixel.html) implementation.
     pub fn new_with_size_and_pitch(width: u32, height: u32, pitch: u32) -> ImageBufferVal<PixelP> {
         let size_in_bytes = PixelP::calc_size_in_bytes(width, height, pitch)
-            .expect(&format!("Invalid combindation of width ({:}), height ({:}) and pitch ({:}) \
-                             for this pixel type",
-                             width,
-                             height,
-                             pitch));
+            .unwrap_or_else(|| {
+                panic!("Invalid combindation of width ({:}), height ({:}) and pitch ({:}) \
+                        for this pixel type",
+                       width,
+                       height,
+                       pitch)
+            });
 
         let data = vec![0; size_in_bytes];
         ImageVal(ImageBuffer {
-            width: width,
-            height: height,
-            pitch: pitch,
+            width,
+            height,
+            pitch,
             raw_data: data,
             _marker: PhantomData,
         })
     }
+
+    /// Construct a new image buffer with the minimum pitch whose pixels are given by `f`.
+    ///
+    /// `f` is called once for every location (`x`, `y`) in row-major order, e.g. to generate
+    /// test patterns.
+    pub fn from_fn<F>(width: u32, height: u32, mut f: F) -> ImageBufferVal<PixelP>
+        where F: FnMut(u32, u32) -> PixelVal<PixelP>
+    {
+        let mut result = Self::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = f(x, y);
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+
+    /// Construct a new image buffer with the specified size and pitch out of a stream of bytes.
+    ///
+    /// The stream has to contain the bytes of all pixels in row-major order without any
+    /// padding, as returned by [`bytes()`](#method.bytes). Therefore it can be used with any
+    /// `pitch` which is valid for the pixel type. Returns `None` if the combination of `width`,
+    /// `height` and `pitch` is invalid or if the stream does not contain exactly
+    /// `height * calc_minimum_pitch(width, 1)` bytes.
+    pub fn from_bytes_iter<I>(width: u32,
+                              height: u32,
+                              pitch: u32,
+                              bytes: I)
+                              -> Option<ImageBufferVal<PixelP>>
+        where I: IntoIterator<Item = u8>
+    {
+        PixelP::calc_size_in_bytes(width, height, pitch)?;
+        let mut result = Self::new_with_size_and_pitch(width, height, pitch);
+        let row_size = PixelP::calc_minimum_pitch(width, 1);
+        let mut bytes = bytes.into_iter();
+        for y in 0..height as usize {
+            let start = y * pitch as usize;
+            for dst in result.0.raw_data[start..start + row_size].iter_mut() {
+                *dst = bytes.next()?;
+            }
+        }
+        match bytes.next() {
+            Some(_) => None,
+            None => Some(result),
+        }
+    }
+
+    /// Construct a new image buffer which takes ownership of an existing raw buffer.
+    ///
+    /// `data` has to use the layout of [`load_from_raw_buffer`](#method.load_from_raw_buffer),
+    /// but in contrast to it nothing is copied. Returns `None` if the combination of `width`,
+    /// `height` and `pitch` is invalid or if the length of `data` differs from the size given
+    /// by [`calc_size_in_bytes`](trait.Pixel.html#tymethod.calc_size_in_bytes).
+    pub fn from_raw_vec(width: u32,
+                        height: u32,
+                        pitch: u32,
+                        data: Vec<u8>)
+                        -> Option<ImageBufferVal<PixelP>> {
+        if PixelP::calc_size_in_bytes(width, height, pitch)? != data.len() {
+            return None;
+        }
+        Some(ImageVal(ImageBuffer {
+            width,
+            height,
+            pitch,
+            raw_data: data,
+            _marker: PhantomData,
+        }))
+    }
+
+    /// Returns an iterator over the bytes of all pixels in row-major order.
+    ///
+    /// In contrast to the raw buffer the padding at the end of every row is skipped, so the
+    /// stream does not depend on the pitch and contains exactly
+    /// `height * calc_minimum_pitch(width, 1)` bytes.
+    pub fn bytes<'a>(&'a self) -> impl Iterator<Item = u8> + 'a {
+        let row_size = PixelP::calc_minimum_pitch(self.width(), 1);
+        let pitch = self.pitch() as usize;
+        let raw_data = &self.0.raw_data;
+        (0..self.height() as usize)
+            .flat_map(move |y| raw_data[y * pitch..y * pitch + row_size].iter().cloned())
+    }
+
+    /// Returns the whole raw buffer without copying.
+    ///
+    /// The padding at the end of every row is included, so the length equals
+    /// [`get_size_in_bytes()`](#method.get_size_in_bytes) and row `y` starts at `y * pitch`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0.raw_data
+    }
+
+    /// Mutable variant of [`as_bytes`](#method.as_bytes).
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.0.raw_data
+    }
+
+    /// Returns the bytes of row `y`, or `None` if `y` is out of bounds.
+    ///
+    /// The padding at the end of the row is excluded, so the slice contains exactly
+    /// `calc_minimum_pitch(width, 1)` bytes, which suits processing with `chunks_exact`.
+    pub fn row_bytes(&self, y: u32) -> Option<&[u8]> {
+        let range = self.row_range(y)?;
+        Some(&self.0.raw_data[range])
+    }
+
+    /// Mutable variant of [`row_bytes`](#method.row_bytes).
+    pub fn row_bytes_mut(&mut self, y: u32) -> Option<&mut [u8]> {
+        let range = self.row_range(y)?;
+        Some(&mut self.0.raw_data[range])
+    }
+
+    fn row_range(&self, y: u32) -> Option<Range<usize>> {
+        if y >= self.height() {
+            return None;
+        }
+        let start = (y as usize) * (self.pitch() as usize);
+        Some(start..start + PixelP::calc_minimum_pitch(self.width(), 1))
+    }
+
+    /// Splits the raw buffer into at most `n` horizontal bands of roughly equal height.
+    ///
+    /// Every band is returned as the index of its first row together with the bytes of its
+    /// rows, including the padding. All slices start on a row boundary, so the usual offset
+    /// `(y - first_row) * pitch + x * pixel_size` is valid within a band. The band heights
+    /// differ by at most one row and empty bands are omitted, i.e. there are fewer than `n`
+    /// bands if the image has fewer than `n` rows. As the slices do not overlap they can be
+    /// processed in parallel, e.g. with scoped threads.
+    ///
+    /// # Panics
+    /// If `n` is zero.
+    pub fn split_rows_mut(&mut self, n: usize) -> Vec<(u32, &mut [u8])> {
+        assert!(n > 0, "The number of bands must not be zero");
+        let height = self.height() as usize;
+        let pitch = self.pitch() as usize;
+        let mut rest = &mut self.0.raw_data[..height * pitch];
+        let mut result = Vec::with_capacity(n.min(height));
+        let mut row = 0;
+        for band in 0..n.min(height) {
+            let rows = height / n + if band < height % n { 1 } else { 0 };
+            let (head, tail) = rest.split_at_mut(rows * pitch);
+            result.push((row as u32, head));
+            rest = tail;
+            row += rows;
+        }
+        result
+    }
+
+    /// Returns an iterator over mutable handles to all pixels in row-major order.
+    ///
+    /// Every [`PixelMut`](struct.PixelMut.html) dereferences to the decoded pixel, changes are
+    /// written back into the buffer when the handle is dropped.
+    pub fn pixels_mut<'a>(&'a mut self) -> impl Iterator<Item = PixelMut<'a, PixelP>> + 'a {
+        let row_size = PixelP::calc_minimum_pitch(self.width(), 1);
+        let pixel_size = PixelP::calc_minimum_pitch(1, 1);
+        let pitch = self.pitch() as usize;
+        let height = self.height() as usize;
+        self.0.raw_data[..height * pitch]
+            .chunks_mut(pitch.max(1))
+            .flat_map(move |row| row[..row_size].chunks_mut(pixel_size))
+            .map(|bytes| {
+                PixelMut {
+                    pixel: PixelVal::load_from_raw_buffer(0, 0, 0, bytes),
+                    bytes,
+                }
+            })
+    }
+}
+
+/// Mutable handle to a single pixel of an [`ImageBuffer`](struct.ImageBuffer.html), see
+/// [`ImageBufferVal::pixels_mut`](struct.ImageVal.html#method.pixels_mut).
+///
+/// The handle holds a decoded copy of the pixel, which is written back into the buffer when the
+/// handle is dropped.
+pub struct PixelMut<'a, PixelP>
+    where PixelP: Pixel
+{
+    pixel: PixelVal<PixelP>,
+    bytes: &'a mut [u8],
+}
+
+impl<'a, PixelP> Deref for PixelMut<'a, PixelP>
+    where PixelP: Pixel
+{
+    type Target = PixelVal<PixelP>;
+    fn deref(&self) -> &PixelVal<PixelP> {
+        &self.pixel
+    }
+}
+
+impl<'a, PixelP> DerefMut for PixelMut<'a, PixelP>
+    where PixelP: Pixel
+{
+    fn deref_mut(&mut self) -> &mut PixelVal<PixelP> {
+        &mut self.pixel
+    }
+}
+
+impl<'a, PixelP> Drop for PixelMut<'a, PixelP>
+    where PixelP: Pixel
+{
+    fn drop(&mut self) {
+        self.pixel.write_into_raw_buffer(0, 0, 0, self.bytes);
+    }
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Copies this image into an already existing image buffer.
+    ///
+    /// Unlike `clone()` this reuses the storage of `dst` whenever it is large enough, which
+    /// avoids an allocation per call in loops processing many images of the same size.
+    /// Afterwards `dst` has the same width, height, pitch and content as this image.
+    pub fn clone_into(&self, dst: &mut ImageBufferVal<ImageP::PixelT>) {
+        let size_in_bytes = self.get_size_in_bytes();
+        let dst = &mut dst.0;
+        dst.width = self.width();
+        dst.height = self.height();
+        dst.pitch = self.pitch();
+        dst.raw_data.resize(size_in_bytes, 0);
+        self.write_into_raw_buffer(&mut dst.raw_data);
+    }
 }
 
 // Implement Image trait for ImageBuffer
@@ -67,11 +293,13 @@ impl<PixelP> Image for ImageBuffer<PixelP>
 
     fn get_size_in_bytes(&self) -> usize {
         PixelP::calc_size_in_bytes(self.width, self.height, self.pitch)
-            .expect(&format!("Invalid combindation of width ({:}), height ({:}) and pitch ({:}) \
-                             for this pixel type - for an image ALREADY CREATED",
-                             self.width,
-                             self.height,
-                             self.pitch))
+            .unwrap_or_else(|| {
+                panic!("Invalid combindation of width ({:}), height ({:}) and pitch ({:}) \
+                        for this pixel type - for an image ALREADY CREATED",
+                       self.width,
+                       self.height,
+                       self.pitch)
+            })
     }
     fn load_from_raw_buffer(&mut self, buffer: &[u8]) {
         assert_eq!(self.get_size_in_bytes(), buffer.len());
@@ -91,14 +319,19 @@ impl<PixelP> Image for ImageBuffer<PixelP>
         self.pitch
     }
     fn get_pixel(&self, x: u32, y: u32) -> Option<PixelP> {
-        if x < self.width || y < self.height {
+        if x < self.width && y < self.height {
             Some(PixelP::load_from_raw_buffer(x, y, self.pitch, &self.raw_data))
         } else {
             None
         }
     }
     fn set_pixel(&mut self, x: u32, y: u32, value: PixelP) {
-        assert!(x < self.width || y < self.height);
+        assert!(x < self.width && y < self.height,
+                "Pixel ({:}, {:}) is outside of the {:}x{:} image",
+                x,
+                y,
+                self.width,
+                self.height);
         value.write_into_raw_buffer(x, y, self.pitch, &mut self.raw_data)
     }
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use {Scalar, ScalarVal, PixelArithmetic, Rgb, RgbVal, Rgba};
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Permutes the channels of every pixel.
+    ///
+    /// Channel `i` of the result is channel `order[i]` of the input, so `[0, 1, 2]` is the
+    /// identity and `[2, 1, 0]` converts between RGB and BGR.
+    ///
+    /// # Panics
+    /// If `order` is not a permutation of `0..CHANNEL_COUNT`.
+    pub fn reorder_channels(&self, order: &[usize]) -> ImageBufferVal<ImageP::PixelT> {
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let mut sorted = order.to_vec();
+        sorted.sort();
+        assert!(sorted.len() == channels && sorted.iter().enumerate().all(|(i, &c)| i == c),
+                "{:?} is not a permutation of {:} channels",
+                order,
+                channels);
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).unwrap();
+                let mut new_pixel = pixel;
+                for (idx, &src) in order.iter().enumerate() {
+                    new_pixel.0.set_channel(idx, pixel.0.get_channel(src));
+                }
+                result.set_pixel(x, y, new_pixel);
+            }
+        }
+        result
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Rgb<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Exchanges the red and the blue channel, i.e. converts between RGB and BGR order.
+    pub fn swap_rb(&self) -> ImageBufferVal<Rgb<ScalarP>> {
+        self.reorder_channels(&[2, 1, 0])
+    }
+
+    /// Corrects a color cast with the gray-world assumption.
+    ///
+    /// The assumption is that the average color of a scene is gray. Every channel is
+    /// therefore scaled by `mean / channel_mean`, where `mean` is the average of the three
+    /// channel means, so all channel means become equal. The values are converted back with
+    /// [`Scalar::from_f64`](trait.Scalar.html#tymethod.from_f64), so integer results
+    /// saturate. A channel with a mean of zero is left unchanged, an image which is already
+    /// balanced is returned unchanged up to rounding.
+    pub fn white_balance_grayworld(&self) -> ImageBufferVal<Rgb<ScalarP>> {
+        let mut means = [0f64; 3];
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).unwrap();
+                for (idx, mean) in means.iter_mut().enumerate() {
+                    *mean += pixel.0.get_channel(idx).to_f64();
+                }
+            }
+        }
+        let mean = means.iter().sum::<f64>() / 3.0;
+        let gains: Vec<f64> = means.iter()
+            .map(|&channel_mean| if channel_mean == 0.0 { 1.0 } else { mean / channel_mean })
+            .collect();
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                for (idx, gain) in gains.iter().enumerate() {
+                    let value = pixel.0.get_channel(idx).to_f64() * gain;
+                    pixel.0.set_channel(idx, Scalar::from_f64(value));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Rgba<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Composites every pixel over the solid color `background` and drops the alpha channel.
+    ///
+    /// Computes `alpha * color + (1 - alpha) * background` per channel with the alpha value
+    /// normalized by the [scalar range](trait.Scalar.html#tymethod.range_min). Opaque pixels
+    /// keep their color and fully transparent pixels become the background.
+    pub fn flatten(&self, background: RgbVal<ScalarP>) -> ImageBufferVal<Rgb<ScalarP>> {
+        let min = ScalarP::range_min().to_f64();
+        let max = ScalarP::range_max().to_f64();
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).unwrap();
+                let alpha = (pixel.a().0.to_f64() - min) / (max - min);
+                let blend = |fg: ScalarP, bg: ScalarP| {
+                    ScalarVal(Scalar::from_f64(alpha * fg.to_f64() + (1.0 - alpha) * bg.to_f64()))
+                };
+                result.set_pixel(x,
+                                 y,
+                                 RgbVal::new(blend(pixel.r().0, background.r().0),
+                                             blend(pixel.g().0, background.g().0),
+                                             blend(pixel.b().0, background.b().0)));
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,76 @@
+use {ScalarVal, Rgb8U, RgbVal8U};
+use {Image, ImageVal, ImageBufferVal, ResizeFilter};
+use image::font::{GLYPH_HEIGHT, GLYPH_ADVANCE};
+
+/// Space in pixels around thumbnails and labels of a contact sheet.
+const SHEET_PADDING: u32 = 4;
+
+impl ImageBufferVal<Rgb8U> {
+    /// Arranges labeled thumbnails of `images` in a grid with `cols` columns (contact sheet).
+    ///
+    /// Every image is scaled with bilinear interpolation to fit into `thumb_w x thumb_h`
+    /// while keeping its aspect ratio and is centered in that box. Its label is drawn below
+    /// in white with the [built-in font](#method.draw_text) and cut off at the width of the
+    /// cell. All cells have the same size of `thumb_w + 8` by `thumb_h + 19` pixels, i.e.
+    /// a padding of 4 pixels around the thumbnail and the label of 7 pixels height. The cells
+    /// are filled row by row, the remaining cells of an incomplete last row stay empty. The
+    /// background is dark gray.
+    ///
+    /// # Panics
+    /// If `images` is empty or `thumb_w`, `thumb_h` or `cols` is zero.
+    pub fn make_contact_sheet<ImageB>(images: &[(String, ImageVal<ImageB>)],
+                                      thumb_w: u32,
+                                      thumb_h: u32,
+                                      cols: u32)
+                                      -> ImageBufferVal<Rgb8U>
+        where ImageB: Image<PixelT = Rgb8U>
+    {
+        assert!(!images.is_empty(), "No images for the contact sheet");
+        assert!(thumb_w > 0 && thumb_h > 0,
+                "Thumbnail size ({:}x{:}) must not be zero",
+                thumb_w,
+                thumb_h);
+        assert!(cols > 0, "Number of columns must not be zero");
+
+        let cell_w = thumb_w + 2 * SHEET_PADDING;
+        let cell_h = thumb_h + GLYPH_HEIGHT + 3 * SHEET_PADDING;
+        let rows = (images.len() as u32).div_ceil(cols);
+        let background = RgbVal8U::new(ScalarVal(48), ScalarVal(48), ScalarVal(48));
+        let text_color = RgbVal8U::new(ScalarVal(255), ScalarVal(255), ScalarVal(255));
+        let max_chars = ((cell_w - SHEET_PADDING) / GLYPH_ADVANCE) as usize;
+
+        let mut sheet = ImageBufferVal::new_with_size(cols * cell_w, rows * cell_h);
+        for y in 0..sheet.height() {
+            for x in 0..sheet.width() {
+                sheet.set_pixel(x, y, background);
+            }
+        }
+
+        for (idx, (label, image)) in images.iter().enumerate() {
+            let cell_x = (idx as u32 % cols) * cell_w;
+            let cell_y = (idx as u32 / cols) * cell_h;
+
+            if image.width() > 0 && image.height() > 0 {
+                let scale = (thumb_w as f64 / image.width() as f64)
+                    .min(thumb_h as f64 / image.height() as f64);
+                let w = ((image.width() as f64 * scale).round() as u32).max(1).min(thumb_w);
+                let h = ((image.height() as f64 * scale).round() as u32).max(1).min(thumb_h);
+                let thumbnail = image.resize(w, h, ResizeFilter::Bilinear);
+                let left = cell_x + SHEET_PADDING + (thumb_w - w) / 2;
+                let top = cell_y + SHEET_PADDING + (thumb_h - h) / 2;
+                for y in 0..h {
+                    for x in 0..w {
+                        sheet.set_pixel(left + x, top + y, thumbnail.get_pixel(x, y).unwrap());
+                    }
+                }
+            }
+
+            let text: String = label.chars().take(max_chars).collect();
+            sheet.draw_text((cell_x + SHEET_PADDING) as i64,
+                            (cell_y + thumb_h + 2 * SHEET_PADDING) as i64,
+                            &text,
+                            text_color);
+        }
+        sheet
+    }
+}
@@ -3,7 +3,7 @@ use std::ops::{Sub, SubAssign};
 use std::ops::{Mul, MulAssign};
 use std::ops::{Div, DivAssign};
 use ScalarVal;
-use {PixelArithmetic, PixelVal};
+use {Pixel, PixelArithmetic, PixelVal, Endianness};
 use {Image, ImageBufferVal};
 
 // TODO: The example below is currently set to 'ignore' because there is an
@@ -80,12 +80,67 @@ impl<ImageP> ImageVal<ImageP>
     }
     #[allow(missing_docs)]
     pub fn get_pixel(&self, x: u32, y: u32) -> Option<PixelVal<ImageP::PixelT>> {
-        self.0.get_pixel(x, y).map(|v| PixelVal(v))
+        self.0.get_pixel(x, y).map(PixelVal)
     }
     #[allow(missing_docs)]
     pub fn set_pixel(&mut self, x: u32, y: u32, value: PixelVal<ImageP::PixelT>) {
         self.0.set_pixel(x, y, value.0)
     }
+    /// Returns an iterator over all pixels in row-major order.
+    pub fn pixels<'a>(&'a self) -> impl Iterator<Item = PixelVal<ImageP::PixelT>> + 'a {
+        self.enumerate_pixels().map(|(_, _, pixel)| pixel)
+    }
+    /// Returns an iterator over all pixels together with their location (`x`, `y`), in
+    /// row-major order.
+    pub fn enumerate_pixels<'a>(&'a self)
+                                -> impl Iterator<Item = (u32, u32, PixelVal<ImageP::PixelT>)> + 'a {
+        let width = self.width();
+        (0..self.height()).flat_map(move |y| {
+            (0..width).map(move |x| (x, y, PixelVal(self.0.get_pixel(x, y).unwrap())))
+        })
+    }
+    /// Loads the pixels out of a raw buffer whose scalars are stored in the given byte order.
+    ///
+    /// The buffer has the same layout as for
+    /// [`load_from_raw_buffer`](#method.load_from_raw_buffer), only the byte order of every
+    /// scalar differs. This allows to read data with a known byte order on any host.
+    ///
+    /// # Panics
+    /// If the size of `buffer` differs from [`get_size_in_bytes`](#method.get_size_in_bytes).
+    pub fn load_from_raw_buffer_with_endianness(&mut self, buffer: &[u8], endianness: Endianness) {
+        assert_eq!(self.get_size_in_bytes(), buffer.len());
+        let pitch = self.pitch();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = ImageP::PixelT::load_from_raw_buffer_with_endianness(x,
+                                                                                 y,
+                                                                                 pitch,
+                                                                                 buffer,
+                                                                                 endianness);
+                self.0.set_pixel(x, y, pixel);
+            }
+        }
+    }
+    /// Writes the pixels into a raw buffer, storing every scalar in the given byte order.
+    ///
+    /// This is the counterpart of
+    /// [`load_from_raw_buffer_with_endianness`](#method.load_from_raw_buffer_with_endianness).
+    /// The padding at the end of every row is left untouched.
+    ///
+    /// # Panics
+    /// If the size of `buffer` differs from [`get_size_in_bytes`](#method.get_size_in_bytes).
+    pub fn write_into_raw_buffer_with_endianness(&self, buffer: &mut [u8], endianness: Endianness) {
+        assert_eq!(self.get_size_in_bytes(), buffer.len());
+        let pitch = self.pitch();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.0
+                    .get_pixel(x, y)
+                    .unwrap()
+                    .write_into_raw_buffer_with_endianness(x, y, pitch, buffer, endianness);
+            }
+        }
+    }
 }
 
 // implement all std ops through PixelArithmetic trait
@@ -0,0 +1,52 @@
+use {Scalar, ScalarVal, PixelArithmetic, Rgb8U, RgbVal8U};
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Visualizes the difference to `other` as a false-color image.
+    ///
+    /// For every pixel the difference `self - other` is averaged over all channels,
+    /// normalized by the [scalar range](trait.Scalar.html#tymethod.range_min), multiplied by
+    /// `amplify` and clamped to `[-1, 1]`. Unchanged pixels are neutral gray `(128, 128, 128)`,
+    /// pixels which are brighter in this image are blended towards pure red and darker ones
+    /// towards pure blue, reaching it at a (amplified) difference of the full range.
+    ///
+    /// # Panics
+    /// If the sizes of both images differ.
+    pub fn diff_image<ImageB>(&self,
+                              other: &ImageVal<ImageB>,
+                              amplify: f64)
+                              -> ImageBufferVal<Rgb8U>
+        where ImageB: Image<PixelT = ImageP::PixelT>
+    {
+        assert!((self.width(), self.height()) == (other.width(), other.height()),
+                "Image sizes differ ({:}x{:} vs. {:}x{:})",
+                self.width(),
+                self.height(),
+                other.width(),
+                other.height());
+
+        let min = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_min().to_f64();
+        let max = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_max().to_f64();
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let a = self.get_pixel(x, y).unwrap();
+                let b = other.get_pixel(x, y).unwrap();
+                let sum: f64 = (0..channels)
+                    .map(|idx| a.0.get_channel(idx).to_f64() - b.0.get_channel(idx).to_f64())
+                    .sum();
+                let d = (sum / channels as f64 / (max - min) * amplify).clamp(-1.0, 1.0);
+                let (target_r, target_b) = if d > 0.0 { (255.0, 0.0) } else { (0.0, 255.0) };
+                let t = d.abs();
+                let mix = |target: f64| ScalarVal(Scalar::from_f64(128.0 + t * (target - 128.0)));
+                result.set_pixel(x, y, RgbVal8U::new(mix(target_r), mix(0.0), mix(target_b)));
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,92 @@
+use PixelVal;
+use {Image, ImageVal};
+use image::font::{glyph, GLYPH_WIDTH, GLYPH_ADVANCE};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Draws a circle with center (`cx`, `cy`) and `radius` in `color`.
+    ///
+    /// A filled circle covers exactly the pixels whose squared distance to the center is at
+    /// most `radius^2`, it is drawn with horizontal spans. The outline is rasterized with the
+    /// midpoint circle algorithm and forms a closed, 8-connected ring of one pixel width.
+    /// The center may lie outside of the image, everything outside of the image is clipped.
+    pub fn draw_circle(&mut self,
+                       cx: i64,
+                       cy: i64,
+                       radius: u32,
+                       color: PixelVal<ImageP::PixelT>,
+                       filled: bool) {
+        let r = radius as i64;
+        if filled {
+            for dy in -r..r + 1 {
+                let half = ((r * r - dy * dy) as f64).sqrt() as i64;
+                for dx in -half..half + 1 {
+                    self.draw_pixel_clipped(cx + dx, cy + dy, color);
+                }
+            }
+            return;
+        }
+
+        let mut x = r;
+        let mut y = 0;
+        let mut error = 1 - r;
+        while x >= y {
+            for &(dx, dy) in &[(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x),
+                               (x, -y)] {
+                self.draw_pixel_clipped(cx + dx, cy + dy, color);
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Draws `text` in `color` with the built-in 5x7 pixel font, the top left corner of the
+    /// first character at (`x`, `y`).
+    ///
+    /// Every character advances 6 pixels to the right, there is no line wrapping and newlines
+    /// aren't interpreted. The font covers the printable ASCII characters up to `'_'`,
+    /// lowercase letters are drawn as uppercase ones and all other characters as `'?'`. Only
+    /// the set pixels of the glyphs are drawn and everything outside of the image is clipped.
+    pub fn draw_text(&mut self, x: i64, y: i64, text: &str, color: PixelVal<ImageP::PixelT>) {
+        for (idx, c) in text.chars().enumerate() {
+            let left = x + (idx as i64) * GLYPH_ADVANCE as i64;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                        self.draw_pixel_clipped(left + col as i64, y + row as i64, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws a grid of one pixel wide horizontal and vertical lines in `color`.
+    ///
+    /// The lines cover every column and every row whose coordinate is a multiple of
+    /// `spacing`, so the grid starts at the origin. All other pixels are left untouched.
+    ///
+    /// # Panics
+    /// If `spacing` is zero.
+    pub fn draw_grid(&mut self, spacing: u32, color: PixelVal<ImageP::PixelT>) {
+        assert!(spacing > 0, "Grid spacing must not be zero");
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                if x % spacing == 0 || y % spacing == 0 {
+                    self.set_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    fn draw_pixel_clipped(&mut self, x: i64, y: i64, color: PixelVal<ImageP::PixelT>) {
+        if x >= 0 && y >= 0 && x < self.width() as i64 && y < self.height() as i64 {
+            self.set_pixel(x as u32, y as u32, color);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use {ScalarVal, PixelArithmetic, Gray8U, GrayVal8U};
+use {Image, ImageVal, ImageBufferVal};
+use io::{PngPixel, encode_png, encode_base64};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PngPixel
+{
+    /// Encodes the image as PNG and wraps it into a `data:image/png;base64,...` URL.
+    ///
+    /// The result can be used directly as `src` of an HTML `<img>` tag, e.g. for quick
+    /// visualization in a notebook. Gray and RGB images are supported, see
+    /// [`PngPixel`](trait.PngPixel.html). The PNG is stored without compression, so the URL is
+    /// about a third larger than the raw pixel data.
+    pub fn to_png_data_url(&self) -> String {
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let mut rows =
+            Vec::with_capacity((self.width() as usize) * (self.height() as usize) * channels);
+        for pixel in self.pixels() {
+            for idx in 0..channels {
+                rows.push(pixel.0.get_channel(idx));
+            }
+        }
+        let png = encode_png(self.width(), self.height(), ImageP::PixelT::COLOR_TYPE, &rows);
+        format!("data:image/png;base64,{:}", encode_base64(&png))
+    }
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray8U>
+{
+    /// Compresses the pixels with a simple run-length encoding.
+    ///
+    /// The pixels are traversed in row-major order and every run of equal intensities is
+    /// stored as a `(count, intensity)` byte pair. Runs longer than 255 pixels are split into
+    /// several pairs. Use [`rle_decompress`](#method.rle_decompress) to restore the image.
+    pub fn rle_compress(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        let mut run: Option<(u8, u8)> = None;
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let value = self.get_pixel(x, y).unwrap().intensity().0;
+                run = match run {
+                    Some((count, current)) if current == value && count < 255 => {
+                        Some((count + 1, current))
+                    }
+                    Some((count, current)) => {
+                        result.extend_from_slice(&[count, current]);
+                        Some((1, value))
+                    }
+                    None => Some((1, value)),
+                };
+            }
+        }
+        if let Some((count, current)) = run {
+            result.extend_from_slice(&[count, current]);
+        }
+        result
+    }
+}
+
+impl ImageBufferVal<Gray8U> {
+    /// Restores an image of size `width` x `height` compressed with
+    /// [`rle_compress`](#method.rle_compress).
+    ///
+    /// Returns `None` if `data` is malformed, i.e. it has an odd length, contains a run of
+    /// length zero or doesn't decode to exactly `width * height` pixels.
+    pub fn rle_decompress(width: u32, height: u32, data: &[u8]) -> Option<ImageBufferVal<Gray8U>> {
+        let len = (width as usize) * (height as usize);
+        if !data.len().is_multiple_of(2) {
+            return None;
+        }
+        let mut pixels = Vec::with_capacity(len);
+        for pair in data.chunks(2) {
+            let (count, value) = (pair[0] as usize, pair[1]);
+            if count == 0 || pixels.len() + count > len {
+                return None;
+            }
+            pixels.extend(::std::iter::repeat_n(value, count));
+        }
+        if pixels.len() != len {
+            return None;
+        }
+
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for (idx, &value) in pixels.iter().enumerate() {
+            let (x, y) = ((idx % width as usize) as u32, (idx / width as usize) as u32);
+            result.set_pixel(x, y, GrayVal8U::new(ScalarVal(value)));
+        }
+        Some(result)
+    }
+}
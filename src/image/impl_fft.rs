@@ -0,0 +1,45 @@
+use {ScalarVal, Complex32F, ComplexVal32F};
+use {Image, ImageVal, ImageBufferVal};
+use image::fft::fft2;
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Complex32F>
+{
+    /// Computes the 2D discrete Fourier transform.
+    ///
+    /// The transform is done as 1D transforms along all rows followed by all columns, with
+    /// `f64` precision internally. It is unnormalized and the zero frequency is located at
+    /// (`0`, `0`). Power-of-two sizes use a radix-2 FFT, other sizes are transformed exactly
+    /// as well, but with the `O(n^2)` definition per row and column.
+    pub fn fft2(&self) -> ImageBufferVal<Complex32F> {
+        self.transform(false)
+    }
+
+    /// Computes the inverse 2D discrete Fourier transform.
+    ///
+    /// The result is scaled by `1 / (width * height)`, so `ifft2(fft2(x))` returns `x`. See
+    /// [`fft2`](#method.fft2).
+    pub fn ifft2(&self) -> ImageBufferVal<Complex32F> {
+        self.transform(true)
+    }
+
+    fn transform(&self, inverse: bool) -> ImageBufferVal<Complex32F> {
+        let width = self.width();
+        let height = self.height();
+        let mut data = Vec::with_capacity((width as usize) * (height as usize));
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = self.get_pixel(x, y).unwrap();
+                data.push((pixel.re().0 as f64, pixel.im().0 as f64));
+            }
+        }
+        fft2(&mut data, width, height, inverse);
+
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for (idx, &(re, im)) in data.iter().enumerate() {
+            let (x, y) = (idx as u32 % width, idx as u32 / width);
+            result.set_pixel(x, y, ComplexVal32F::new(ScalarVal(re as f32), ScalarVal(im as f32)));
+        }
+        result
+    }
+}
@@ -0,0 +1,401 @@
+use {Scalar, ScalarVal, PixelArithmetic, Kernel, Gray, Gray32F, GrayVal32F};
+use {Image, ImageVal, ImageBufferVal, BorderMode};
+use image::float_image::FloatImage;
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Applies an arithmetic mean filter with a rectangular window of `kx` x `ky` pixels.
+    ///
+    /// Every output pixel is the channel-wise average of the window around the corresponding
+    /// input pixel. The window reaches `(kx - 1) / 2` pixels to the left and `kx / 2` pixels to
+    /// the right of the center (vertically likewise), so odd window sizes are centered.
+    /// Locations outside of the image are replaced by the closest border pixel.
+    ///
+    /// Using different sizes for `kx` and `ky` allows to smooth along one axis only.
+    ///
+    /// # Panics
+    /// If `kx` or `ky` is zero.
+    pub fn mean_filter(&self, kx: u32, ky: u32) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(kx > 0 && ky > 0, "Window size ({:}x{:}) must not be zero", kx, ky);
+
+        let width = self.width();
+        let height = self.height();
+        let count = (kx as f64) * (ky as f64);
+        let mut sums = vec![0f64; ImageP::PixelT::CHANNEL_COUNT];
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                for sum in sums.iter_mut() {
+                    *sum = 0.0;
+                }
+                for wy in 0..ky {
+                    let sy = BorderMode::Clamp
+                        .resolve(y as i64 + wy as i64 - ((ky - 1) / 2) as i64, height)
+                        .unwrap();
+                    for wx in 0..kx {
+                        let sx = BorderMode::Clamp
+                            .resolve(x as i64 + wx as i64 - ((kx - 1) / 2) as i64, width)
+                            .unwrap();
+                        let pixel = self.get_pixel(sx, sy).unwrap();
+                        for (idx, sum) in sums.iter_mut().enumerate() {
+                            *sum += pixel.0.get_channel(idx).to_f64();
+                        }
+                    }
+                }
+
+                let mut new_pixel = self.get_pixel(x, y).unwrap();
+                for (idx, sum) in sums.iter().enumerate() {
+                    new_pixel.0.set_channel(idx, Scalar::from_f64(sum / count));
+                }
+                result.set_pixel(x, y, new_pixel);
+            }
+        }
+        result
+    }
+
+    /// Averages every pixel over the `(2 * radius + 1)` x `(2 * radius + 1)` window centered
+    /// on it.
+    ///
+    /// This equals [`mean_filter`](#method.mean_filter) with a window size of
+    /// `2 * radius + 1`, but the average is computed separably, first horizontally and then
+    /// vertically, in `f64`. Locations outside of the image are replaced by the closest
+    /// border pixel. A radius of zero returns a copy of the image.
+    pub fn box_blur(&self, radius: u32) -> ImageBufferVal<ImageP::PixelT> {
+        let size = 2 * radius as usize + 1;
+        let weights = vec![1.0 / size as f64; size];
+        FloatImage::from_image(self).convolve_separable(&weights, &weights).to_image()
+    }
+
+    /// Replaces every pixel by the channel-wise median of the `(2 * radius + 1)` x
+    /// `(2 * radius + 1)` window centered on it.
+    ///
+    /// In contrast to a mean filter this removes isolated outliers like salt and pepper noise
+    /// completely while keeping edges sharp. Locations outside of the image are replaced by
+    /// the closest border pixel. A radius of zero returns a copy of the image.
+    ///
+    /// # Panics
+    /// If a window contains values which can not be ordered, i.e. floating point NaN.
+    pub fn median_filter(&self, radius: u32) -> ImageBufferVal<ImageP::PixelT> {
+        let (width, height) = (self.width(), self.height());
+        let radius = radius as i64;
+        let mut values = Vec::with_capacity(((2 * radius + 1) * (2 * radius + 1)) as usize);
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut new_pixel = self.get_pixel(x, y).unwrap();
+                for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                    values.clear();
+                    for dy in -radius..radius + 1 {
+                        let sy = BorderMode::Clamp.resolve(y as i64 + dy, height).unwrap();
+                        for dx in -radius..radius + 1 {
+                            let sx = BorderMode::Clamp.resolve(x as i64 + dx, width).unwrap();
+                            values.push(self.get_pixel(sx, sy).unwrap().0.get_channel(idx));
+                        }
+                    }
+                    let middle = values.len() / 2;
+                    let (_, median, _) = values.select_nth_unstable_by(middle, |a, b| {
+                        a.partial_cmp(b).expect("Can not order NaN values")
+                    });
+                    new_pixel.0.set_channel(idx, *median);
+                }
+                result.set_pixel(x, y, new_pixel);
+            }
+        }
+        result
+    }
+
+    /// Convolves the image with `kernel`.
+    ///
+    /// The kernel is flipped by 180 degrees before it is applied, which is the mathematical
+    /// definition of a convolution. Locations outside of the image are handled according to
+    /// `border`. The weighted sums are accumulated as `f64` and converted back with
+    /// [`Scalar::from_f64`](trait.Scalar.html#tymethod.from_f64), so integer results saturate.
+    pub fn convolve(&self,
+                    kernel: &Kernel<<ImageP::PixelT as PixelArithmetic>::ScalarT>,
+                    border: BorderMode)
+                    -> ImageBufferVal<ImageP::PixelT> {
+        self.apply_kernel(kernel, border, true)
+    }
+
+    /// Cross-correlates the image with `kernel`.
+    ///
+    /// In contrast to [`convolve`](#method.convolve) the kernel is applied as it is, without
+    /// flipping it. For symmetric kernels both operations yield the same result.
+    pub fn correlate(&self,
+                     kernel: &Kernel<<ImageP::PixelT as PixelArithmetic>::ScalarT>,
+                     border: BorderMode)
+                     -> ImageBufferVal<ImageP::PixelT> {
+        self.apply_kernel(kernel, border, false)
+    }
+
+    fn apply_kernel(&self,
+                    kernel: &Kernel<<ImageP::PixelT as PixelArithmetic>::ScalarT>,
+                    border: BorderMode,
+                    flip: bool)
+                    -> ImageBufferVal<ImageP::PixelT> {
+        let width = self.width();
+        let height = self.height();
+        let center_x = (kernel.width() / 2) as i64;
+        let center_y = (kernel.height() / 2) as i64;
+        let mut sums = vec![0f64; ImageP::PixelT::CHANNEL_COUNT];
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                for sum in sums.iter_mut() {
+                    *sum = 0.0;
+                }
+                for ky in 0..kernel.height() {
+                    let dy = if flip { center_y - ky as i64 } else { ky as i64 - center_y };
+                    let sy = match border.resolve(y as i64 + dy, height) {
+                        Some(sy) => sy,
+                        None => continue,
+                    };
+                    for kx in 0..kernel.width() {
+                        let dx = if flip { center_x - kx as i64 } else { kx as i64 - center_x };
+                        let sx = match border.resolve(x as i64 + dx, width) {
+                            Some(sx) => sx,
+                            None => continue,
+                        };
+                        let weight = kernel.get(kx, ky).0.to_f64();
+                        let pixel = self.get_pixel(sx, sy).unwrap();
+                        for (idx, sum) in sums.iter_mut().enumerate() {
+                            *sum += weight * pixel.0.get_channel(idx).to_f64();
+                        }
+                    }
+                }
+
+                let mut new_pixel = self.get_pixel(x, y).unwrap();
+                for (idx, sum) in sums.iter().enumerate() {
+                    new_pixel.0.set_channel(idx, Scalar::from_f64(*sum));
+                }
+                result.set_pixel(x, y, new_pixel);
+            }
+        }
+        result
+    }
+
+    /// Blurs the image with a Gaussian kernel of standard deviation `sigma`.
+    ///
+    /// The kernel is truncated at a radius of `ceil(3 * sigma)` and applied separably.
+    /// Locations outside of the image are clamped. A `sigma` of zero returns a copy.
+    ///
+    /// # Panics
+    /// If `sigma` is negative or not finite.
+    pub fn gaussian_blur(&self, sigma: f64) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(sigma >= 0.0 && sigma.is_finite(), "Invalid sigma ({:})", sigma);
+        FloatImage::from_image(self).gaussian_blur(sigma).to_image()
+    }
+
+    /// Sharpens the image by adding the difference to a blurred version of itself.
+    ///
+    /// Computes `in + amount * (in - gaussian_blur(in, sigma))`. The computation is done in
+    /// `f64`, so integer images saturate instead of wrapping around. An `amount` of zero
+    /// returns the unchanged image.
+    ///
+    /// # Panics
+    /// If `sigma` is negative or not finite.
+    pub fn unsharp_mask(&self, sigma: f64, amount: f64) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(sigma >= 0.0 && sigma.is_finite(), "Invalid sigma ({:})", sigma);
+        let original = FloatImage::from_image(self);
+        let blurred = original.gaussian_blur(sigma);
+        original.zip_with(&blurred, |v, b| v + amount * (v - b)).to_image()
+    }
+
+    /// Applies an edge-preserving bilateral filter.
+    ///
+    /// Every neighbor in the `(2 * radius + 1) x (2 * radius + 1)` window is weighted by a
+    /// Gaussian of its spatial distance (`sigma_spatial`, in pixels) multiplied with a Gaussian
+    /// of its intensity difference to the center pixel (`sigma_range`, in scalar units; the
+    /// Euclidean distance over all channels). Neighbors across a strong edge therefore hardly
+    /// contribute, while flat regions are smoothed. A very large `sigma_range` degrades this to
+    /// a plain Gaussian blur. Locations outside of the image are clamped.
+    ///
+    /// The runtime is `O(radius^2)` per pixel.
+    ///
+    /// # Panics
+    /// If `sigma_spatial` or `sigma_range` is not positive.
+    pub fn bilateral(&self,
+                     sigma_spatial: f64,
+                     sigma_range: f64,
+                     radius: u32)
+                     -> ImageBufferVal<ImageP::PixelT> {
+        assert!(sigma_spatial > 0.0, "Invalid spatial sigma ({:})", sigma_spatial);
+        assert!(sigma_range > 0.0, "Invalid range sigma ({:})", sigma_range);
+
+        let width = self.width();
+        let height = self.height();
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let radius = radius as i64;
+        let source = FloatImage::from_image(self);
+        let mut result = FloatImage::new(width, height, channels);
+        let mut sums = vec![0f64; channels];
+        for y in 0..height {
+            for x in 0..width {
+                for sum in sums.iter_mut() {
+                    *sum = 0.0;
+                }
+                let mut total_weight = 0.0;
+                for dy in -radius..radius + 1 {
+                    let sy = BorderMode::Clamp.resolve(y as i64 + dy, height).unwrap();
+                    for dx in -radius..radius + 1 {
+                        let sx = BorderMode::Clamp.resolve(x as i64 + dx, width).unwrap();
+                        let range_sq: f64 = (0..channels)
+                            .map(|c| source.get(sx, sy, c) - source.get(x, y, c))
+                            .map(|d| d * d)
+                            .sum();
+                        let spatial_sq = (dx * dx + dy * dy) as f64;
+                        let weight = (-spatial_sq / (2.0 * sigma_spatial * sigma_spatial) -
+                                      range_sq / (2.0 * sigma_range * sigma_range))
+                            .exp();
+                        total_weight += weight;
+                        for (c, sum) in sums.iter_mut().enumerate() {
+                            *sum += weight * source.get(sx, sy, c);
+                        }
+                    }
+                }
+                for (c, sum) in sums.iter().enumerate() {
+                    result.set(x, y, c, sum / total_weight);
+                }
+            }
+        }
+        result.to_image()
+    }
+
+    /// Computes the gradient energy of every pixel.
+    ///
+    /// The energy is the sum of the absolute central differences in `x` and `y`, i.e.
+    /// `|p(x + 1, y) - p(x - 1, y)| + |p(x, y + 1) - p(x, y - 1)|`, summed over all channels in
+    /// scalar units. It is zero in flat regions and large across edges. Locations outside of
+    /// the image are clamped, so the border pixels use one-sided differences.
+    pub fn energy_map(&self) -> ImageBufferVal<Gray32F> {
+        let width = self.width();
+        let height = self.height();
+        let value = |x: i64, y: i64, c: usize| {
+            let sx = BorderMode::Clamp.resolve(x, width).unwrap();
+            let sy = BorderMode::Clamp.resolve(y, height).unwrap();
+            self.get_pixel(sx, sy).unwrap().0.get_channel(c).to_f64()
+        };
+
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let (x, y) = (x as i64, y as i64);
+                let energy: f64 = (0..ImageP::PixelT::CHANNEL_COUNT)
+                    .map(|c| {
+                        (value(x + 1, y, c) - value(x - 1, y, c)).abs() +
+                        (value(x, y + 1, c) - value(x, y - 1, c)).abs()
+                    })
+                    .sum();
+                result.set_pixel(x as u32, y as u32, GrayVal32F::new(ScalarVal(energy as f32)));
+            }
+        }
+        result
+    }
+
+    /// Denoises the image with non-local means.
+    ///
+    /// Every output pixel is a weighted average of the pixels in the
+    /// `(2 * search + 1) x (2 * search + 1)` window around it. The weight of a candidate is
+    /// `exp(-d^2 / h^2)`, where `d^2` is the mean squared difference (in scalar units, over all
+    /// channels) between the `(2 * patch + 1) x (2 * patch + 1)` patches around the candidate
+    /// and around the pixel itself. Smaller values of `h` therefore average only very similar
+    /// patches, larger ones smooth more. As its own patch always matches perfectly, the pixel
+    /// itself gets the largest weight of all other candidates instead of `1`, otherwise it
+    /// would dominate the average. Locations outside of the image are clamped.
+    ///
+    /// The runtime is `O(search^2 * patch^2)` per pixel.
+    ///
+    /// # Panics
+    /// If `h` is not positive.
+    pub fn nl_means(&self, h: f64, patch: u32, search: u32) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(h > 0.0, "Invalid filter strength ({:})", h);
+
+        let width = self.width();
+        let height = self.height();
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let patch = patch as i64;
+        let search = search as i64;
+        let source = FloatImage::from_image(self);
+        let value = |x: i64, y: i64, c: usize| {
+            let sx = BorderMode::Clamp.resolve(x, width).unwrap();
+            let sy = BorderMode::Clamp.resolve(y, height).unwrap();
+            source.get(sx, sy, c)
+        };
+        let patch_count = ((2 * patch + 1) * (2 * patch + 1)) as f64 * channels as f64;
+
+        let mut result = FloatImage::new(width, height, channels);
+        let mut sums = vec![0f64; channels];
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                for sum in sums.iter_mut() {
+                    *sum = 0.0;
+                }
+                let mut total_weight = 0.0;
+                let mut max_weight = 0.0f64;
+                for dy in -search..search + 1 {
+                    for dx in -search..search + 1 {
+                        if dx == 0 && dy == 0 {
+                            continue;
+                        }
+                        let mut distance = 0.0;
+                        for py in -patch..patch + 1 {
+                            for px in -patch..patch + 1 {
+                                for c in 0..channels {
+                                    let d = value(x + dx + px, y + dy + py, c) -
+                                            value(x + px, y + py, c);
+                                    distance += d * d;
+                                }
+                            }
+                        }
+                        let weight = (-distance / patch_count / (h * h)).exp();
+                        max_weight = max_weight.max(weight);
+                        total_weight += weight;
+                        for (c, sum) in sums.iter_mut().enumerate() {
+                            *sum += weight * value(x + dx, y + dy, c);
+                        }
+                    }
+                }
+                // a lone pixel without candidates keeps its value
+                let self_weight = if total_weight > 0.0 { max_weight } else { 1.0 };
+                total_weight += self_weight;
+                for (c, sum) in sums.iter().enumerate() {
+                    let mean = (sum + self_weight * value(x, y, c)) / total_weight;
+                    result.set(x as u32, y as u32, c, mean);
+                }
+            }
+        }
+        result.to_image()
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Applies the discrete Laplace operator `[[0, 1, 0], [1, -4, 1], [0, 1, 0]]`.
+    ///
+    /// The result is the sum of the second derivatives in `x` and `y`. It is zero in flat
+    /// regions and along linear gradients and changes its sign across edges, so the output is
+    /// `Gray32F` regardless of the input type. Locations outside of the image are clamped.
+    pub fn laplacian(&self) -> ImageBufferVal<Gray32F> {
+        let width = self.width();
+        let height = self.height();
+        let value = |x: u32, y: u32, dx: i64, dy: i64| {
+            let sx = BorderMode::Clamp.resolve(x as i64 + dx, width).unwrap();
+            let sy = BorderMode::Clamp.resolve(y as i64 + dy, height).unwrap();
+            self.get_pixel(sx, sy).unwrap().intensity().0.to_f64()
+        };
+
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let sum = value(x, y, 0, -1) + value(x, y, -1, 0) + value(x, y, 1, 0) +
+                          value(x, y, 0, 1) - 4.0 * value(x, y, 0, 0);
+                result.set_pixel(x, y, GrayVal32F::new(ScalarVal(sum as f32)));
+            }
+        }
+        result
+    }
+}
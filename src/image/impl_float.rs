@@ -0,0 +1,285 @@
+use {Scalar, ScalarVal, Gray, Gray32F, GrayVal32F, Binary, BinaryVal, Kernel};
+use {Image, ImageVal, ImageBufferVal};
+use image::fft::{Complex64, fft2};
+
+/// Computes the 2D Fourier transform of the intensities of `image`.
+fn spectrum<ImageP>(image: &ImageVal<ImageP>) -> Vec<Complex64>
+    where ImageP: Image<PixelT = Gray32F>
+{
+    let mut data = Vec::with_capacity((image.width() as usize) * (image.height() as usize));
+    for y in 0..image.height() {
+        for x in 0..image.width() {
+            data.push((image.get_pixel(x, y).unwrap().intensity().0 as f64, 0.0));
+        }
+    }
+    fft2(&mut data, image.width(), image.height(), false);
+    data
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray32F>
+{
+    /// Raises every intensity to the power of `e`.
+    ///
+    /// This follows `f32::powf`, so e.g. negative intensities with a fractional exponent
+    /// result in `NaN`.
+    pub fn powf(&self, e: f32) -> ImageBufferVal<Gray32F> {
+        self.map_intensity(|v| v.powf(e))
+    }
+
+    /// Computes the square root of every intensity.
+    ///
+    /// Negative intensities result in `NaN`.
+    pub fn sqrt(&self) -> ImageBufferVal<Gray32F> {
+        self.map_intensity(f32::sqrt)
+    }
+
+    /// Computes `ln(1 + v)` of every intensity `v`.
+    ///
+    /// This compresses a large dynamic range and is the core of logarithmic tone mapping.
+    /// Zero maps to zero, [`expm1`](#method.expm1) is the inverse.
+    pub fn log1p(&self) -> ImageBufferVal<Gray32F> {
+        self.map_intensity(f32::ln_1p)
+    }
+
+    /// Computes `exp(v) - 1` of every intensity `v`, the inverse of [`log1p`](#method.log1p).
+    pub fn expm1(&self) -> ImageBufferVal<Gray32F> {
+        self.map_intensity(f32::exp_m1)
+    }
+
+    /// Blends `frame` into the image: `self = (1 - alpha) * self + alpha * frame`.
+    ///
+    /// Used repeatedly this is an exponential moving average, e.g. a background model over a
+    /// stream of video frames. The image is updated in place, `alpha = 1` replaces it with the
+    /// frame and `alpha = 0` leaves it unchanged. Frame intensities are taken as they are, so
+    /// integer frames are not normalized to `[0, 1]`.
+    ///
+    /// # Panics
+    /// If the image sizes differ or `alpha` is outside of `[0, 1]`.
+    pub fn accumulate_weighted<ImageB, ScalarB>(&mut self, frame: &ImageVal<ImageB>, alpha: f64)
+        where ImageB: Image<PixelT = Gray<ScalarB>>,
+              ScalarB: Scalar
+    {
+        assert!(self.width() == frame.width() && self.height() == frame.height(),
+                "Frame size ({:}x{:}) differs from accumulator size ({:}x{:})",
+                frame.width(),
+                frame.height(),
+                self.width(),
+                self.height());
+        assert!((0.0..=1.0).contains(&alpha), "Invalid alpha ({:})", alpha);
+
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let old = self.get_pixel(x, y).unwrap().intensity().0 as f64;
+                let new = frame.get_pixel(x, y).unwrap().intensity().0.to_f64();
+                let value = if alpha == 1.0 { new } else { (1.0 - alpha) * old + alpha * new };
+                self.set_pixel(x, y, GrayVal32F::new(ScalarVal(value as f32)));
+            }
+        }
+    }
+
+    /// Marks the pixels where `frame` deviates from the image by more than `threshold`.
+    ///
+    /// The image is treated as background model (see
+    /// [`accumulate_weighted`](#method.accumulate_weighted)), a pixel of the returned mask is
+    /// set if `|frame - background| > threshold`. The difference is computed in `f64`.
+    ///
+    /// # Panics
+    /// If the image sizes differ.
+    pub fn foreground_mask<ImageB, ScalarB>(&self,
+                                            frame: &ImageVal<ImageB>,
+                                            threshold: f64)
+                                            -> ImageBufferVal<Binary>
+        where ImageB: Image<PixelT = Gray<ScalarB>>,
+              ScalarB: Scalar
+    {
+        assert!(self.width() == frame.width() && self.height() == frame.height(),
+                "Frame size ({:}x{:}) differs from background size ({:}x{:})",
+                frame.width(),
+                frame.height(),
+                self.width(),
+                self.height());
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let background = self.get_pixel(x, y).unwrap().intensity().0 as f64;
+                let value = frame.get_pixel(x, y).unwrap().intensity().0.to_f64();
+                result.set_pixel(x, y, BinaryVal::new((value - background).abs() > threshold));
+            }
+        }
+        result
+    }
+
+    /// Estimates the translation between this image and `other` by phase correlation.
+    ///
+    /// Returns (`dx`, `dy`) such that `other(x, y)` is approximately `self(x - dx, y - dy)`.
+    /// Both images are transformed into the frequency domain, the peak of the inverse
+    /// transform of their normalized cross-power spectrum gives the integer shift and a
+    /// parabola fit through the neighbors of the peak refines it to sub-pixel accuracy. As the
+    /// Fourier transform is periodic, shifts are found modulo the image size and reported in
+    /// the range `[-size / 2, size / 2)`. Power-of-two sizes are considerably faster.
+    ///
+    /// # Panics
+    /// If the image sizes differ.
+    pub fn estimate_shift<ImageB>(&self, other: &ImageVal<ImageB>) -> (f64, f64)
+        where ImageB: Image<PixelT = Gray32F>
+    {
+        assert!(self.width() == other.width() && self.height() == other.height(),
+                "Image sizes differ ({:}x{:} vs {:}x{:})",
+                self.width(),
+                self.height(),
+                other.width(),
+                other.height());
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+        if width == 0 || height == 0 {
+            return (0.0, 0.0);
+        }
+
+        let a = spectrum(self);
+        let b = spectrum(other);
+
+        // normalized cross-power spectrum conj(A) * B / |conj(A) * B|
+        let mut correlation: Vec<Complex64> = a.iter()
+            .zip(b.iter())
+            .map(|(&(ar, ai), &(br, bi))| {
+                let (re, im) = (ar * br + ai * bi, ar * bi - ai * br);
+                let norm = re.hypot(im);
+                if norm > 1e-12 { (re / norm, im / norm) } else { (0.0, 0.0) }
+            })
+            .collect();
+        fft2(&mut correlation, width as u32, height as u32, true);
+
+        let value = |x: usize, y: usize| correlation[(y % height) * width + x % width].0;
+        let (mut peak_x, mut peak_y) = (0, 0);
+        for y in 0..height {
+            for x in 0..width {
+                if value(x, y) > value(peak_x, peak_y) {
+                    peak_x = x;
+                    peak_y = y;
+                }
+            }
+        }
+
+        let refine = |prev: f64, peak: f64, next: f64| {
+            let denominator = prev - 2.0 * peak + next;
+            if denominator.abs() > 1e-12 { 0.5 * (prev - next) / denominator } else { 0.0 }
+        };
+        let dx = peak_x as f64 +
+                 refine(value(peak_x + width - 1, peak_y),
+                        value(peak_x, peak_y),
+                        value(peak_x + 1, peak_y));
+        let dy = peak_y as f64 +
+                 refine(value(peak_x, peak_y + height - 1),
+                        value(peak_x, peak_y),
+                        value(peak_x, peak_y + 1));
+        let wrap = |d: f64, len: usize| if d >= len as f64 / 2.0 { d - len as f64 } else { d };
+        (wrap(dx, width), wrap(dy, height))
+    }
+
+    /// Convolves the image with `kernel` by multiplication in the frequency domain.
+    ///
+    /// The result is the same as [`convolve`](#method.convolve) with `BorderMode::Zero` up to
+    /// rounding errors, but the runtime doesn't depend on the size of the kernel, which pays
+    /// off for large kernels. Image and kernel are zero padded to a common power-of-two size
+    /// which holds the full linear convolution, so nothing wraps around at the borders.
+    pub fn convolve_fft(&self, kernel: &Kernel<f32>) -> ImageBufferVal<Gray32F> {
+        let width = self.width();
+        let height = self.height();
+        let padded_width = (width + kernel.width() - 1).next_power_of_two();
+        let padded_height = (height + kernel.height() - 1).next_power_of_two();
+        let len = (padded_width as usize) * (padded_height as usize);
+        let idx = |x: u32, y: u32| (y as usize) * (padded_width as usize) + x as usize;
+
+        let mut image = vec![(0.0, 0.0); len];
+        for y in 0..height {
+            for x in 0..width {
+                image[idx(x, y)].0 = self.get_pixel(x, y).unwrap().intensity().0 as f64;
+            }
+        }
+        let mut weights = vec![(0.0, 0.0); len];
+        for y in 0..kernel.height() {
+            for x in 0..kernel.width() {
+                weights[idx(x, y)].0 = kernel.get(x, y).0 as f64;
+            }
+        }
+        fft2(&mut image, padded_width, padded_height, false);
+        fft2(&mut weights, padded_width, padded_height, false);
+        for (a, b) in image.iter_mut().zip(weights.iter()) {
+            *a = (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0);
+        }
+        fft2(&mut image, padded_width, padded_height, true);
+
+        // the full convolution starts half a kernel before the image
+        let (offset_x, offset_y) = (kernel.width() / 2, kernel.height() / 2);
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let value = image[idx(x + offset_x, y + offset_y)].0 as f32;
+                result.set_pixel(x, y, GrayVal32F::new(ScalarVal(value)));
+            }
+        }
+        result
+    }
+
+    /// Removes all spatial frequencies above `cutoff` (ideal low-pass filter).
+    ///
+    /// `cutoff` is a radius in cycles per pixel, so `0.5` is the Nyquist frequency along the
+    /// axes and `~0.71` along the diagonal. The filter is applied to the Fourier transform of
+    /// the image, whose zero frequency is located at the corner; frequencies are measured with
+    /// the indices beyond the half size mapped to negative values. The inverse transform is
+    /// real up to rounding errors, its real part is returned.
+    pub fn lowpass(&self, cutoff: f64) -> ImageBufferVal<Gray32F> {
+        self.filter_frequencies(|radius| radius <= cutoff)
+    }
+
+    /// Removes all spatial frequencies up to `cutoff` (ideal high-pass filter).
+    ///
+    /// This is the complement of [`lowpass`](#method.lowpass): both results add up to the
+    /// original image. Note that the mean is removed as well.
+    pub fn highpass(&self, cutoff: f64) -> ImageBufferVal<Gray32F> {
+        self.filter_frequencies(|radius| radius > cutoff)
+    }
+
+    fn filter_frequencies<F>(&self, keep: F) -> ImageBufferVal<Gray32F>
+        where F: Fn(f64) -> bool
+    {
+        let width = self.width();
+        let height = self.height();
+        let frequency = |idx: u32, len: u32| {
+            let idx = if idx > len / 2 { idx as f64 - len as f64 } else { idx as f64 };
+            idx / len as f64
+        };
+
+        let mut data = spectrum(self);
+        for y in 0..height {
+            for x in 0..width {
+                if !keep(frequency(x, width).hypot(frequency(y, height))) {
+                    data[(y as usize) * (width as usize) + x as usize] = (0.0, 0.0);
+                }
+            }
+        }
+        fft2(&mut data, width, height, true);
+
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for (idx, &(re, _)) in data.iter().enumerate() {
+            let (x, y) = (idx as u32 % width, idx as u32 / width);
+            result.set_pixel(x, y, GrayVal32F::new(ScalarVal(re as f32)));
+        }
+        result
+    }
+
+    fn map_intensity<F>(&self, f: F) -> ImageBufferVal<Gray32F>
+        where F: Fn(f32) -> f32
+    {
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let value = self.get_pixel(x, y).unwrap().intensity().0;
+                result.set_pixel(x, y, GrayVal32F::new(ScalarVal(f(value))));
+            }
+        }
+        result
+    }
+}
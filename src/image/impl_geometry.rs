@@ -0,0 +1,149 @@
+use std::f64::consts::PI;
+
+use {PixelArithmetic, Gray32F};
+use {Image, ImageVal, ImageBufferVal, BorderMode};
+use image::float_image::FloatImage;
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Applies a perspective transformation given by the 3x3 homography `h`.
+    ///
+    /// `h` is stored in row-major order and maps homogeneous output coordinates
+    /// (`x`, `y`, `1`) to input coordinates, i.e. every output pixel is looked up at
+    /// (`x' / w'`, `y' / w'`) in this image with (`x'`, `y'`, `w'`) = `h * (x, y, 1)`. The
+    /// input is sampled with bilinear interpolation and locations outside of the image are
+    /// handled according to `border`. Output pixels whose homogeneous coordinate `w'` is zero
+    /// map to infinity, they are outside of the image and handled according to `border` as
+    /// well.
+    pub fn warp_perspective(&self,
+                            h: [f64; 9],
+                            out_width: u32,
+                            out_height: u32,
+                            border: BorderMode)
+                            -> ImageBufferVal<ImageP::PixelT> {
+        let source = FloatImage::from_image(self);
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let mut result = FloatImage::new(out_width, out_height, channels);
+        for y in 0..out_height {
+            for x in 0..out_width {
+                let (xf, yf) = (x as f64, y as f64);
+                let w = h[6] * xf + h[7] * yf + h[8];
+                let sx = (h[0] * xf + h[1] * yf + h[2]) / w;
+                let sy = (h[3] * xf + h[4] * yf + h[5]) / w;
+                for c in 0..channels {
+                    result.set(x, y, c, source.sample_bilinear(sx, sy, c, border));
+                }
+            }
+        }
+        result.to_image()
+    }
+
+    /// Remaps the image with explicit coordinate maps (backward mapping).
+    ///
+    /// The output has the size of the maps and every output pixel (`x`, `y`) is sampled with
+    /// bilinear interpolation at (`map_x[x, y]`, `map_y[x, y]`) in this image, where pixel
+    /// centers are located at integer coordinates. Identity maps therefore reproduce the
+    /// image. Locations outside of the image are handled according to `border`.
+    ///
+    /// # Panics
+    /// If the sizes of `map_x` and `map_y` differ.
+    pub fn remap<ImageX, ImageY>(&self,
+                                 map_x: &ImageVal<ImageX>,
+                                 map_y: &ImageVal<ImageY>,
+                                 border: BorderMode)
+                                 -> ImageBufferVal<ImageP::PixelT>
+        where ImageX: Image<PixelT = Gray32F>,
+              ImageY: Image<PixelT = Gray32F>
+    {
+        let (width, height) = (map_x.width(), map_x.height());
+        assert!((width, height) == (map_y.width(), map_y.height()),
+                "Sizes of the coordinate maps differ");
+
+        let source = FloatImage::from_image(self);
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let mut result = FloatImage::new(width, height, channels);
+        for y in 0..height {
+            for x in 0..width {
+                let sx = map_x.get_pixel(x, y).unwrap().intensity().0 as f64;
+                let sy = map_y.get_pixel(x, y).unwrap().intensity().0 as f64;
+                for c in 0..channels {
+                    result.set(x, y, c, source.sample_bilinear(sx, sy, c, border));
+                }
+            }
+        }
+        result.to_image()
+    }
+
+    /// Extracts a `size x size` patch centered at the sub-pixel location `center`, rotated
+    /// counterclockwise by `angle` (in radians).
+    ///
+    /// The patch pixel (`i`, `j`) is sampled with bilinear interpolation at
+    /// `center + R(angle) * (i - (size - 1) / 2, j - (size - 1) / 2)`, where `R` is the
+    /// rotation matrix. For an odd `size`, an integer `center` and an `angle` of zero this is
+    /// a plain crop. Locations outside of the image are clamped.
+    pub fn sample_patch(&self,
+                        center: (f64, f64),
+                        size: u32,
+                        angle: f64)
+                        -> ImageBufferVal<ImageP::PixelT> {
+        let source = FloatImage::from_image(self);
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let (sin, cos) = angle.sin_cos();
+        let offset = (size as f64 - 1.0) / 2.0;
+        let mut result = FloatImage::new(size, size, channels);
+        for j in 0..size {
+            for i in 0..size {
+                let u = i as f64 - offset;
+                let v = j as f64 - offset;
+                let sx = center.0 + u * cos - v * sin;
+                let sy = center.1 + u * sin + v * cos;
+                for c in 0..channels {
+                    result.set(i, j, c, source.sample_bilinear(sx, sy, c, BorderMode::Clamp));
+                }
+            }
+        }
+        result.to_image()
+    }
+
+    /// Remaps the image into log-polar coordinates around `center`.
+    ///
+    /// The output column `x` corresponds to the radius `r = exp(x / out_width * ln(r_max))`,
+    /// where `r_max` is the distance from `center` to the farthest image corner, and the
+    /// output row `y` to the angle `2 * pi * y / out_height`, measured from the positive `x`
+    /// axis towards the positive `y` axis. Concentric circles therefore become vertical lines,
+    /// a rotation about `center` becomes a vertical (cyclic) shift and a scaling becomes a
+    /// horizontal shift. The input is sampled with bilinear interpolation, locations outside
+    /// of the image are zero.
+    pub fn to_log_polar(&self,
+                        center: (f64, f64),
+                        out_width: u32,
+                        out_height: u32)
+                        -> ImageBufferVal<ImageP::PixelT> {
+        let source = FloatImage::from_image(self);
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let max_radius = [(0.0, 0.0),
+                          (self.width() as f64 - 1.0, 0.0),
+                          (0.0, self.height() as f64 - 1.0),
+                          (self.width() as f64 - 1.0, self.height() as f64 - 1.0)]
+            .iter()
+            .map(|&(cx, cy)| (cx - center.0).hypot(cy - center.1))
+            .fold(1.0, f64::max);
+        let log_scale = max_radius.ln() / out_width as f64;
+
+        let mut result = FloatImage::new(out_width, out_height, channels);
+        for y in 0..out_height {
+            let (sin, cos) = (2.0 * PI * y as f64 / out_height as f64).sin_cos();
+            for x in 0..out_width {
+                let radius = (x as f64 * log_scale).exp();
+                let sx = center.0 + radius * cos;
+                let sy = center.1 + radius * sin;
+                for c in 0..channels {
+                    result.set(x, y, c, source.sample_bilinear(sx, sy, c, BorderMode::Zero));
+                }
+            }
+        }
+        result.to_image()
+    }
+}
@@ -0,0 +1,109 @@
+use {ScalarVal, Gray8U, GrayVal8U};
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray8U>
+{
+    /// Counts the number of pixels for every intensity.
+    ///
+    /// The result has 256 bins, bin `i` holds the number of pixels with intensity `i`.
+    pub fn histogram(&self) -> Vec<u64> {
+        let mut bins = vec![0u64; 256];
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                bins[self.get_pixel(x, y).unwrap().intensity().0 as usize] += 1;
+            }
+        }
+        bins
+    }
+
+    /// Computes a global threshold with Otsu's method.
+    ///
+    /// The threshold maximizes the between-class variance of the pixels with an intensity up to
+    /// and including the threshold and the pixels above it.
+    pub fn otsu_threshold(&self) -> ScalarVal<u8> {
+        let bins = self.histogram();
+        let total: u64 = bins.iter().sum();
+        let total_sum: f64 = bins.iter().enumerate().map(|(i, &n)| i as f64 * n as f64).sum();
+
+        let mut best = (0, -1.0);
+        let mut count_low = 0u64;
+        let mut sum_low = 0.0;
+        for (t, &n) in bins.iter().enumerate() {
+            count_low += n;
+            sum_low += t as f64 * n as f64;
+            let count_high = total - count_low;
+            if count_low == 0 || count_high == 0 {
+                continue;
+            }
+            let mean_low = sum_low / count_low as f64;
+            let mean_high = (total_sum - sum_low) / count_high as f64;
+            let variance = count_low as f64 * count_high as f64 * (mean_low - mean_high) *
+                           (mean_low - mean_high);
+            if variance > best.1 {
+                best = (t, variance);
+            }
+        }
+        ScalarVal(best.0 as u8)
+    }
+
+    /// Computes the Shannon entropy of the intensity histogram in bits (base 2 logarithm).
+    ///
+    /// The result is `-sum(p_i * log2(p_i))` over all intensities with a non-zero probability
+    /// `p_i`. It is zero for a constant image and reaches its maximum of 8 bits if all 256
+    /// intensities occur equally often. An empty image has an entropy of zero.
+    pub fn entropy(&self) -> f64 {
+        let bins = self.histogram();
+        let total = bins.iter().sum::<u64>() as f64;
+        bins.iter()
+            .filter(|&&n| n > 0)
+            .map(|&n| {
+                let p = n as f64 / total;
+                p * (1.0 / p).log2()
+            })
+            .sum()
+    }
+
+    /// Remaps the intensities so that the histogram approximates the one of `reference`.
+    ///
+    /// Every intensity `s` is mapped to the smallest intensity `r` for which the cumulative
+    /// distribution of `reference` reaches the cumulative distribution of this image at `s`
+    /// (inverse CDF mapping). In contrast to histogram equalization the target distribution
+    /// is taken from another image. Matching an image to itself returns it unchanged.
+    ///
+    /// # Panics
+    /// If `reference` has no pixels.
+    pub fn match_histogram<ImageB>(&self, reference: &ImageVal<ImageB>) -> ImageBufferVal<Gray8U>
+        where ImageB: Image<PixelT = Gray8U>
+    {
+        let cdf = |bins: Vec<u64>| -> Vec<f64> {
+            // an empty image would divide by zero, its lookup table is never used anyway
+            let total = bins.iter().sum::<u64>().max(1);
+            let mut cumulative = 0;
+            bins.iter()
+                .map(|&n| {
+                    cumulative += n;
+                    cumulative as f64 / total as f64
+                })
+                .collect()
+        };
+        let reference_bins = reference.histogram();
+        assert!(reference_bins.iter().any(|&n| n > 0), "Reference image has no pixels");
+        let source_cdf = cdf(self.histogram());
+        let reference_cdf = cdf(reference_bins);
+
+        // both distributions end at exactly 1, so a match always exists
+        let lookup: Vec<u8> = source_cdf.iter()
+            .map(|&p| reference_cdf.iter().position(|&q| q >= p).unwrap() as u8)
+            .collect();
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let value = lookup[self.get_pixel(x, y).unwrap().intensity().0 as usize];
+                result.set_pixel(x, y, GrayVal8U::new(ScalarVal(value)));
+            }
+        }
+        result
+    }
+}
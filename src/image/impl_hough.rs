@@ -0,0 +1,75 @@
+use std::cmp::Reverse;
+use std::f64::consts::PI;
+
+use Binary;
+use {Image, ImageVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Binary>
+{
+    /// Detects straight lines through the set pixels with the Hough transform.
+    ///
+    /// Every set pixel votes for all lines `rho = x * cos(theta) + y * sin(theta)` through
+    /// it, with `theta` sampled in steps of `theta_res` over `[0, pi)` and `rho` quantized in
+    /// steps of `rho_res` over the range `[-diagonal, diagonal]` of the image. Returned are
+    /// the (`rho`, `theta`) pairs of all accumulator cells with more than `threshold` votes
+    /// which are also a local maximum of their 3x3 accumulator neighborhood (on plateaus only
+    /// the first cell in row-major order counts), sorted by descending number of votes.
+    ///
+    /// # Panics
+    /// If `rho_res` or `theta_res` is not positive.
+    pub fn hough_lines(&self, rho_res: f64, theta_res: f64, threshold: u32) -> Vec<(f64, f64)> {
+        assert!(rho_res > 0.0 && theta_res > 0.0,
+                "Invalid resolutions (rho {:}, theta {:})",
+                rho_res,
+                theta_res);
+
+        let diagonal = (self.width() as f64).hypot(self.height() as f64);
+        let rho_offset = (diagonal / rho_res).ceil() as i64;
+        let rho_bins = 2 * rho_offset as usize + 1;
+        let theta_bins = (PI / theta_res).ceil() as usize;
+        let trig: Vec<(f64, f64)> = (0..theta_bins)
+            .map(|t| (t as f64 * theta_res).sin_cos())
+            .collect();
+
+        let mut votes = vec![0u32; theta_bins * rho_bins];
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                if !self.get_pixel(x, y).unwrap().value() {
+                    continue;
+                }
+                for (t, &(sin, cos)) in trig.iter().enumerate() {
+                    let rho = x as f64 * cos + y as f64 * sin;
+                    let r = ((rho / rho_res).round() as i64 + rho_offset) as usize;
+                    votes[t * rho_bins + r] += 1;
+                }
+            }
+        }
+
+        let mut peaks = Vec::new();
+        for t in 0..theta_bins {
+            for r in 0..rho_bins {
+                let count = votes[t * rho_bins + r];
+                if count <= threshold {
+                    continue;
+                }
+                let mut is_peak = true;
+                for nt in t.saturating_sub(1)..(t + 2).min(theta_bins) {
+                    for nr in r.saturating_sub(1)..(r + 2).min(rho_bins) {
+                        let other = votes[nt * rho_bins + nr];
+                        let earlier = (nt, nr) < (t, r);
+                        if other > count || (earlier && other == count) {
+                            is_peak = false;
+                        }
+                    }
+                }
+                if is_peak {
+                    let rho = (r as i64 - rho_offset) as f64 * rho_res;
+                    peaks.push((count, rho, t as f64 * theta_res));
+                }
+            }
+        }
+        peaks.sort_by_key(|&(count, _, _)| Reverse(count));
+        peaks.into_iter().map(|(_, rho, theta)| (rho, theta)).collect()
+    }
+}
@@ -0,0 +1,63 @@
+use PixelVal;
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Applies `f` to every pixel and returns the results as a new image.
+    ///
+    /// The result has the same size and pitch as this image. This is the most general point
+    /// operation, e.g. for lookup tables or gamma curves.
+    pub fn map<F>(&self, f: F) -> ImageBufferVal<ImageP::PixelT>
+        where F: Fn(PixelVal<ImageP::PixelT>) -> PixelVal<ImageP::PixelT>
+    {
+        let mut result =
+            ImageBufferVal::new_with_size_and_pitch(self.width(), self.height(), self.pitch());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                result.set_pixel(x, y, f(self.get_pixel(x, y).unwrap()));
+            }
+        }
+        result
+    }
+
+    /// Applies `f` to every pixel, like [`map`](#method.map) but without allocating a new
+    /// image.
+    pub fn map_in_place<F>(&mut self, f: F)
+        where F: Fn(PixelVal<ImageP::PixelT>) -> PixelVal<ImageP::PixelT>
+    {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = f(self.get_pixel(x, y).unwrap());
+                self.set_pixel(x, y, pixel);
+            }
+        }
+    }
+
+    /// Combines every pixel with the pixel at the same location in `other`.
+    ///
+    /// This generalizes the arithmetic operators on images, e.g. to compute the pixel-wise
+    /// maximum or absolute difference. The result has the size of this image.
+    ///
+    /// # Panics
+    /// If the sizes of both images differ.
+    pub fn zip_with<ImageB, F>(&self,
+                               other: &ImageVal<ImageB>,
+                               f: F)
+                               -> ImageBufferVal<ImageP::PixelT>
+        where ImageB: Image<PixelT = ImageP::PixelT>,
+              F: Fn(PixelVal<ImageP::PixelT>, PixelVal<ImageP::PixelT>) -> PixelVal<ImageP::PixelT>
+    {
+        assert_eq!(self.width(), other.width());
+        assert_eq!(self.height(), other.height());
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = f(self.get_pixel(x, y).unwrap(), other.get_pixel(x, y).unwrap());
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,155 @@
+use {Scalar, ScalarVal, PixelArithmetic, Gray};
+use {Image, ImageVal};
+use image::float_image::FloatImage;
+use image::integral::IntegralImage;
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Computes the mean structural similarity index (SSIM) between this image and `other`.
+    ///
+    /// The index is evaluated for every `window x window` window which lies completely inside
+    /// of the images, using the local means, variances and the covariance of both images. The
+    /// result is the mean over all windows and channels. The stabilizing constants are
+    /// `(0.01 * L)^2` and `(0.03 * L)^2` with `L` being the size of the
+    /// [scalar range](trait.Scalar.html#tymethod.range_min).
+    ///
+    /// Identical images yield exactly `1.0`, lower values indicate less similarity.
+    ///
+    /// # Panics
+    /// If the dimensions of both images differ, if `window` is zero or if `window` is larger
+    /// than the images.
+    pub fn ssim<ImageB>(&self, other: &ImageVal<ImageB>, window: u32) -> f64
+        where ImageB: Image<PixelT = ImageP::PixelT>
+    {
+        assert_eq!(self.width(), other.width());
+        assert_eq!(self.height(), other.height());
+        assert!(window > 0 && window <= self.width() && window <= self.height(),
+                "Invalid window size ({:}) for an image of size {:}x{:}",
+                window,
+                self.width(),
+                self.height());
+
+        let min = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_min().to_f64();
+        let max = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_max().to_f64();
+        let c1 = (0.01 * (max - min)) * (0.01 * (max - min));
+        let c2 = (0.03 * (max - min)) * (0.03 * (max - min));
+
+        let width = self.width();
+        let height = self.height();
+        let a = FloatImage::from_image(self);
+        let b = FloatImage::from_image(other);
+        let n = (window as f64) * (window as f64);
+        let mut total = 0.0;
+        let mut count = 0.0;
+        for c in 0..ImageP::PixelT::CHANNEL_COUNT {
+            let sum_a = IntegralImage::new(width, height, |x, y| a.get(x, y, c));
+            let sum_b = IntegralImage::new(width, height, |x, y| b.get(x, y, c));
+            let sum_aa = IntegralImage::new(width, height, |x, y| a.get(x, y, c) * a.get(x, y, c));
+            let sum_bb = IntegralImage::new(width, height, |x, y| b.get(x, y, c) * b.get(x, y, c));
+            let sum_ab = IntegralImage::new(width, height, |x, y| a.get(x, y, c) * b.get(x, y, c));
+            for y in 0..height - window + 1 {
+                for x in 0..width - window + 1 {
+                    let (x1, y1) = (x + window, y + window);
+                    let mean_a = sum_a.sum(x, y, x1, y1) / n;
+                    let mean_b = sum_b.sum(x, y, x1, y1) / n;
+                    let var_a = sum_aa.sum(x, y, x1, y1) / n - mean_a * mean_a;
+                    let var_b = sum_bb.sum(x, y, x1, y1) / n - mean_b * mean_b;
+                    let covar = sum_ab.sum(x, y, x1, y1) / n - mean_a * mean_b;
+                    total += ((2.0 * mean_a * mean_b + c1) * (2.0 * covar + c2)) /
+                             ((mean_a * mean_a + mean_b * mean_b + c1) * (var_a + var_b + c2));
+                    count += 1.0;
+                }
+            }
+        }
+        total / count
+    }
+
+    /// Sums all channel values of all pixels.
+    ///
+    /// The sum is accumulated in `f64`, so it does not overflow for integer images. Resamplers
+    /// which average over areas approximately conserve this sum relative to the pixel count.
+    pub fn total_intensity(&self) -> f64 {
+        let mut sum = 0.0;
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pixel = self.get_pixel(x, y).unwrap();
+                for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                    sum += pixel.0.get_channel(idx).to_f64();
+                }
+            }
+        }
+        sum
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Returns the smallest intensity, or `None` for an empty image.
+    ///
+    /// The result is unspecified if the image contains NaN values.
+    pub fn min_intensity(&self) -> Option<ScalarVal<ScalarP>> {
+        self.pixels().map(|p| p.intensity()).fold(None, |acc, v| match acc {
+            Some(m) => Some(m.min(v)),
+            None => Some(v),
+        })
+    }
+
+    /// Returns the largest intensity, or `None` for an empty image.
+    ///
+    /// The result is unspecified if the image contains NaN values.
+    pub fn max_intensity(&self) -> Option<ScalarVal<ScalarP>> {
+        self.pixels().map(|p| p.intensity()).fold(None, |acc, v| match acc {
+            Some(m) => Some(m.max(v)),
+            None => Some(v),
+        })
+    }
+
+    /// Sums all intensities.
+    ///
+    /// For gray images this is the same as [`total_intensity`](#method.total_intensity), the
+    /// sum is accumulated in `f64`, so it does not overflow for integer images.
+    pub fn sum_intensity(&self) -> f64 {
+        self.total_intensity()
+    }
+
+    /// Computes the mean of all intensities in `f64`, which is `NaN` for an empty image.
+    ///
+    /// This equals the mean returned by [`mean_stddev`](#method.mean_stddev), but skips the
+    /// computation of the standard deviation.
+    pub fn mean_intensity(&self) -> f64 {
+        self.sum_intensity() / ((self.width() as f64) * (self.height() as f64))
+    }
+
+    /// Computes the mean and the (population) standard deviation of all intensities.
+    ///
+    /// Both are `NaN` for an empty image.
+    pub fn mean_stddev(&self) -> (f64, f64) {
+        let count = (self.width() as f64) * (self.height() as f64);
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let value = self.get_pixel(x, y).unwrap().intensity().0.to_f64();
+                sum += value;
+                sum_sq += value * value;
+            }
+        }
+        let mean = sum / count;
+        (mean, (sum_sq / count - mean * mean).max(0.0).sqrt())
+    }
+
+    /// Measures the sharpness of the image as the variance of its
+    /// [Laplacian](#method.laplacian).
+    ///
+    /// Blurring removes high frequencies and therefore lowers the score, which makes it a
+    /// simple focus measure, e.g. to select the sharpest of several frames of the same scene.
+    /// Scores are only comparable between images of similar content and intensity range.
+    pub fn sharpness_variance_of_laplacian(&self) -> f64 {
+        let (_, stddev) = self.laplacian().mean_stddev();
+        stddev * stddev
+    }
+}
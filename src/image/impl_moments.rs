@@ -0,0 +1,104 @@
+use {Scalar, Binary, Gray};
+use {Image, ImageVal};
+
+/// Raw and central image moments up to the second order, see
+/// [`ImageVal::moments`](struct.ImageVal.html#method.moments) and
+/// [`ImageVal::intensity_moments`](struct.ImageVal.html#method.intensity_moments).
+///
+/// The raw moments are `m_pq = sum(x^p * y^q * w(x, y))` over all pixels, with the pixel
+/// weight `w`. The central moments `mu_pq` are the same sums relative to the centroid, so they
+/// don't depend on the position of the shape.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Moments {
+    /// Total weight, the area for binary images.
+    pub m00: f64,
+    /// First order moment in `x`.
+    pub m10: f64,
+    /// First order moment in `y`.
+    pub m01: f64,
+    /// Second order central moment in `x`.
+    pub mu20: f64,
+    /// Second order central mixed moment.
+    pub mu11: f64,
+    /// Second order central moment in `y`.
+    pub mu02: f64,
+}
+
+impl Moments {
+    /// Computes the moments from the weights of all pixels in row-major order.
+    fn from_weights<I>(width: u32, weights: I) -> Moments
+        where I: Iterator<Item = f64> + Clone
+    {
+        let position = |idx: usize| ((idx % width as usize) as f64, (idx / width as usize) as f64);
+        let mut moments = Moments {
+            m00: 0.0,
+            m10: 0.0,
+            m01: 0.0,
+            mu20: 0.0,
+            mu11: 0.0,
+            mu02: 0.0,
+        };
+        for (idx, w) in weights.clone().enumerate() {
+            let (x, y) = position(idx);
+            moments.m00 += w;
+            moments.m10 += x * w;
+            moments.m01 += y * w;
+        }
+        // central moments are summed in a second pass, which is numerically more stable
+        let (cx, cy) = moments.centroid();
+        for (idx, w) in weights.enumerate() {
+            let (x, y) = position(idx);
+            moments.mu20 += (x - cx) * (x - cx) * w;
+            moments.mu11 += (x - cx) * (y - cy) * w;
+            moments.mu02 += (y - cy) * (y - cy) * w;
+        }
+        moments
+    }
+
+    /// Returns the (`x`, `y`) center of mass `(m10 / m00, m01 / m00)`.
+    ///
+    /// If the total weight is zero the result is `NaN`.
+    pub fn centroid(&self) -> (f64, f64) {
+        (self.m10 / self.m00, self.m01 / self.m00)
+    }
+
+    /// Returns the angle of the major axis in radians within `(-pi / 2, pi / 2]`.
+    ///
+    /// The angle is measured from the `x` axis towards the `y` axis, i.e. clockwise as `y`
+    /// points down. Shapes without a dominant direction, e.g. a square, have an angle of zero.
+    pub fn orientation(&self) -> f64 {
+        0.5 * (2.0 * self.mu11).atan2(self.mu20 - self.mu02)
+    }
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Binary>
+{
+    /// Computes the [`Moments`](struct.Moments.html) of the set pixels.
+    ///
+    /// Every set pixel has a weight of one, so `m00` is the number of set pixels.
+    pub fn moments(&self) -> Moments {
+        let width = self.width();
+        let weights = (0..self.height())
+            .flat_map(move |y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| if self.get_pixel(x, y).unwrap().value() { 1.0 } else { 0.0 });
+        Moments::from_weights(width, weights)
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Computes the [`Moments`](struct.Moments.html) with the intensities as weights.
+    ///
+    /// The weights are the intensities in scalar units, so `m00` is the sum of all
+    /// intensities. Negative intensities are used as they are.
+    pub fn intensity_moments(&self) -> Moments {
+        let width = self.width();
+        let weights = (0..self.height())
+            .flat_map(move |y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.get_pixel(x, y).unwrap().intensity().0.to_f64());
+        Moments::from_weights(width, weights)
+    }
+}
@@ -0,0 +1,105 @@
+use {Scalar, ScalarVal, PixelArithmetic, Gray};
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Replaces every channel value by the minimum of its `(2 * radius + 1)` square
+    /// neighborhood (grayscale erosion).
+    ///
+    /// Bright structures shrink by `radius` pixels. Only locations inside of the image are
+    /// considered, so the border does not erode the image.
+    pub fn erode(&self, radius: u32) -> ImageBufferVal<ImageP::PixelT> {
+        self.extremum_filter(radius, false)
+    }
+
+    /// Replaces every channel value by the maximum of its `(2 * radius + 1)` square
+    /// neighborhood (grayscale dilation).
+    ///
+    /// Bright structures grow by `radius` pixels. Only locations inside of the image are
+    /// considered.
+    pub fn dilate(&self, radius: u32) -> ImageBufferVal<ImageP::PixelT> {
+        self.extremum_filter(radius, true)
+    }
+
+    /// Erodes and then dilates the image with the same square structuring element.
+    ///
+    /// Bright specks which do not contain the `(2 * radius + 1)` square vanish, while larger
+    /// bright shapes are restored to their original outline.
+    pub fn open(&self, radius: u32) -> ImageBufferVal<ImageP::PixelT> {
+        self.erode(radius).dilate(radius)
+    }
+
+    /// Dilates and then erodes the image with the same square structuring element.
+    ///
+    /// This is the dual of [`open`](#method.open): dark holes which do not contain the
+    /// `(2 * radius + 1)` square are filled, while larger dark regions are preserved.
+    pub fn close(&self, radius: u32) -> ImageBufferVal<ImageP::PixelT> {
+        self.dilate(radius).erode(radius)
+    }
+
+    fn extremum_filter(&self, radius: u32, take_max: bool) -> ImageBufferVal<ImageP::PixelT> {
+        let width = self.width();
+        let height = self.height();
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut new_pixel = self.get_pixel(x, y).unwrap();
+                let x_end = x.saturating_add(radius).saturating_add(1).min(width);
+                let y_end = y.saturating_add(radius).saturating_add(1).min(height);
+                for sy in y.saturating_sub(radius)..y_end {
+                    for sx in x.saturating_sub(radius)..x_end {
+                        let pixel = self.get_pixel(sx, sy).unwrap();
+                        for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                            let value = pixel.0.get_channel(idx).to_f64();
+                            let current = new_pixel.0.get_channel(idx).to_f64();
+                            if (take_max && value > current) || (!take_max && value < current) {
+                                new_pixel.0.set_channel(idx, pixel.0.get_channel(idx));
+                            }
+                        }
+                    }
+                }
+                result.set_pixel(x, y, new_pixel);
+            }
+        }
+        result
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Returns the (`x`, `y`) coordinates of all local maxima in row-major order.
+    ///
+    /// A pixel qualifies if its intensity is above `min_value` and strictly larger than every
+    /// other intensity in its `(2 * radius + 1)` square neighborhood. Pixels on a plateau of
+    /// equal values are therefore never reported. At the border only the part of the
+    /// neighborhood inside of the image is considered.
+    pub fn local_maxima(&self, radius: u32, min_value: ScalarVal<ScalarP>) -> Vec<(u32, u32)> {
+        let width = self.width();
+        let height = self.height();
+        let value = |x, y| self.get_pixel(x, y).unwrap().intensity().0.to_f64();
+        let min_value = min_value.0.to_f64();
+
+        let mut result = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let center = value(x, y);
+                if center <= min_value {
+                    continue;
+                }
+                let x_end = x.saturating_add(radius).saturating_add(1).min(width);
+                let y_end = y.saturating_add(radius).saturating_add(1).min(height);
+                let is_maximum = (y.saturating_sub(radius)..y_end)
+                    .flat_map(|sy| (x.saturating_sub(radius)..x_end).map(move |sx| (sx, sy)))
+                    .all(|(sx, sy)| (sx, sy) == (x, y) || value(sx, sy) < center);
+                if is_maximum {
+                    result.push((x, y));
+                }
+            }
+        }
+        result
+    }
+}
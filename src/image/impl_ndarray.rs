@@ -0,0 +1,48 @@
+use ndarray::Array3;
+use PixelArithmetic;
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Copies the image into an `ndarray` array of shape (`height`, `width`, `channels`).
+    ///
+    /// Only available with the `ndarray` feature.
+    pub fn to_ndarray(&self) -> Array3<<ImageP::PixelT as PixelArithmetic>::ScalarT> {
+        let channels = ImageP::PixelT::CHANNEL_COUNT;
+        let shape = (self.height() as usize, self.width() as usize, channels);
+        Array3::from_shape_fn(shape, |(y, x, c)| {
+            self.get_pixel(x as u32, y as u32).unwrap().0.get_channel(c)
+        })
+    }
+}
+
+impl<PixelP> ImageBufferVal<PixelP>
+    where PixelP: PixelArithmetic
+{
+    /// Constructs an image buffer with minimum pitch out of an `ndarray` array of shape
+    /// (`height`, `width`, `channels`).
+    ///
+    /// Returns `None` if the number of channels does not match the pixel type.
+    ///
+    /// Only available with the `ndarray` feature.
+    pub fn from_ndarray(array: &Array3<PixelP::ScalarT>) -> Option<ImageBufferVal<PixelP>> {
+        let (height, width, channels) = array.dim();
+        if channels != PixelP::CHANNEL_COUNT {
+            return None;
+        }
+
+        let mut result = ImageBufferVal::<PixelP>::new_with_size(width as u32, height as u32);
+        for y in 0..height {
+            for x in 0..width {
+                let mut pixel = result.get_pixel(x as u32, y as u32).unwrap();
+                for c in 0..channels {
+                    pixel.0.set_channel(c, array[[y, x, c]]);
+                }
+                result.set_pixel(x as u32, y as u32, pixel);
+            }
+        }
+        Some(result)
+    }
+}
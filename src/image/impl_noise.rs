@@ -0,0 +1,68 @@
+use {Scalar, PixelArithmetic};
+use {Image, ImageVal, ImageBufferVal};
+use image::rng::Rng;
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Adds zero-mean Gaussian noise with standard deviation `sigma` (in scalar units) to
+    /// every channel.
+    ///
+    /// The noise is generated by a pseudo random number generator initialized with `seed`, so
+    /// the same seed always yields the same result. The values are converted back with
+    /// [`Scalar::from_f64`](trait.Scalar.html#tymethod.from_f64), so integer results saturate.
+    ///
+    /// # Panics
+    /// If `sigma` is negative or not finite.
+    pub fn add_gaussian_noise(&self, sigma: f64, seed: u64) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(sigma >= 0.0 && sigma.is_finite(), "Invalid sigma ({:})", sigma);
+
+        let mut rng = Rng::new(seed);
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                    let value = pixel.0.get_channel(idx).to_f64() + sigma * rng.next_gaussian();
+                    pixel.0.set_channel(idx, Scalar::from_f64(value));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+
+    /// Corrupts pixels with salt-and-pepper noise.
+    ///
+    /// Every pixel is replaced with probability `prob`, in equal parts by the minimum
+    /// (pepper) and the maximum (salt) of the
+    /// [scalar range](trait.Scalar.html#tymethod.range_min), all channels at once. The
+    /// noise is generated by a pseudo random number generator initialized with `seed`, so the
+    /// same seed always yields the same result.
+    ///
+    /// # Panics
+    /// If `prob` is not within `[0, 1]`.
+    pub fn add_salt_pepper(&self, prob: f64, seed: u64) -> ImageBufferVal<ImageP::PixelT> {
+        assert!((0.0..=1.0).contains(&prob), "Invalid probability ({:})", prob);
+
+        let min = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_min();
+        let max = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_max();
+        let mut rng = Rng::new(seed);
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                let draw = rng.next_f64();
+                if draw < prob {
+                    let value = if draw < prob / 2.0 { min } else { max };
+                    for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                        pixel.0.set_channel(idx, value);
+                    }
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+}
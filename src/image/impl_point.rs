@@ -0,0 +1,137 @@
+use {Scalar, ScalarVal, PixelArithmetic};
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Reduces the number of intensity levels of every channel to `levels` (posterize).
+    ///
+    /// The output levels are evenly spaced over the
+    /// [scalar range](trait.Scalar.html#tymethod.range_min) and every value is mapped to the
+    /// nearest of them. With `levels = 2` this is a hard threshold at the middle of the range,
+    /// with as many levels as the integer type can represent this is the identity.
+    ///
+    /// # Panics
+    /// If `levels` is smaller than 2.
+    pub fn quantize(&self, levels: u32) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(levels >= 2, "At least two levels are required, got {:}", levels);
+
+        let min = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_min().to_f64();
+        let max = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_max().to_f64();
+        let steps = (levels - 1) as f64;
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                    let value = pixel.0.get_channel(idx).to_f64();
+                    let level = ((value - min) / (max - min) * steps).round().max(0.0).min(steps);
+                    pixel.0.set_channel(idx, Scalar::from_f64(min + level * (max - min) / steps));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+
+    /// Decodes sRGB encoded values into linear light.
+    ///
+    /// This uses the exact piecewise sRGB transfer function: values up to `0.04045` (relative
+    /// to the [scalar range](trait.Scalar.html#tymethod.range_min)) are on the linear segment
+    /// and divided by `12.92`, larger values follow `((v + 0.055) / 1.055)^2.4`. The
+    /// computation is done in `f64`. Every channel except for the
+    /// [alpha channel](trait.PixelArithmetic.html#associatedconstant.ALPHA_CHANNEL) is converted.
+    pub fn srgb_to_linear(&self) -> ImageBufferVal<ImageP::PixelT> {
+        self.map_normalized(|v| if v <= 0.04045 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        })
+    }
+
+    /// Encodes linear light values with the sRGB transfer function.
+    ///
+    /// This is the inverse of [`srgb_to_linear`](#method.srgb_to_linear): values up to
+    /// `0.0031308` are multiplied by `12.92`, larger values follow `1.055 * v^(1/2.4) - 0.055`.
+    /// The alpha channel is left unchanged.
+    pub fn linear_to_srgb(&self) -> ImageBufferVal<ImageP::PixelT> {
+        self.map_normalized(|v| if v <= 0.0031308 {
+            v * 12.92
+        } else {
+            1.055 * v.powf(1.0 / 2.4) - 0.055
+        })
+    }
+
+    /// Linearly rescales all channel values so that the current minimum maps to `lo` and the
+    /// current maximum to `hi`.
+    ///
+    /// Minimum and maximum are taken over all channels except for the
+    /// [alpha channel](trait.PixelArithmetic.html#associatedconstant.ALPHA_CHANNEL), which is
+    /// left unchanged. The mapping is computed in `f64`, see
+    /// [`Scalar::from_f64`](trait.Scalar.html#tymethod.from_f64) for the rounding behavior.
+    /// `hi` may be smaller than `lo`, which inverts the image. If all values are equal, every
+    /// value is mapped to `lo`.
+    pub fn normalize(&self,
+                     lo: ScalarVal<<ImageP::PixelT as PixelArithmetic>::ScalarT>,
+                     hi: ScalarVal<<ImageP::PixelT as PixelArithmetic>::ScalarT>)
+                     -> ImageBufferVal<ImageP::PixelT> {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for pixel in self.pixels() {
+            for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                if Some(idx) == ImageP::PixelT::ALPHA_CHANNEL {
+                    continue;
+                }
+                let value = pixel.0.get_channel(idx).to_f64();
+                min = min.min(value);
+                max = max.max(value);
+            }
+        }
+        let lo = lo.0.to_f64();
+        let hi = hi.0.to_f64();
+        let scale = if max > min { (hi - lo) / (max - min) } else { 0.0 };
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                    if Some(idx) == ImageP::PixelT::ALPHA_CHANNEL {
+                        continue;
+                    }
+                    let value = pixel.0.get_channel(idx).to_f64();
+                    pixel.0.set_channel(idx, Scalar::from_f64(lo + (value - min) * scale));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+
+    /// Applies `f` to every channel value except for alpha, normalized from the scalar range to
+    /// `[0, 1]`.
+    fn map_normalized<F>(&self, f: F) -> ImageBufferVal<ImageP::PixelT>
+        where F: Fn(f64) -> f64
+    {
+        let min = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_min().to_f64();
+        let max = <<ImageP::PixelT as PixelArithmetic>::ScalarT as Scalar>::range_max().to_f64();
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                    if Some(idx) == ImageP::PixelT::ALPHA_CHANNEL {
+                        continue;
+                    }
+                    let value = (pixel.0.get_channel(idx).to_f64() - min) / (max - min);
+                    pixel.0.set_channel(idx, Scalar::from_f64(min + f(value) * (max - min)));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+}
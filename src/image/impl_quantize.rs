@@ -0,0 +1,103 @@
+use {ScalarVal, Rgb8U, RgbVal8U, Palette, PaletteVal};
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Rgb8U>
+{
+    /// Reduces the image to a palette of at most `colors` colors with the median-cut
+    /// algorithm.
+    ///
+    /// Starting with a box containing all pixels, the box with the largest extent along one
+    /// channel is repeatedly split at the median of that channel, until there are `colors`
+    /// boxes or no box contains different colors anymore. The palette consists of the
+    /// (rounded) mean colors of the boxes, so an image with at most `colors` distinct colors
+    /// is reproduced exactly. Every pixel is then mapped to the index of the nearest palette
+    /// color (Euclidean distance in RGB, the lowest index on ties).
+    ///
+    /// Returns the indexed image together with the palette.
+    ///
+    /// # Panics
+    /// If `colors` is not within `[1, 256]`.
+    pub fn quantize_median_cut(&self, colors: usize) -> (ImageBufferVal<Palette>, Vec<RgbVal8U>) {
+        assert!((1..=256).contains(&colors),
+                "Invalid number of colors ({:}), must be within [1, 256]",
+                colors);
+
+        let width = self.width();
+        let height = self.height();
+        let pixels: Vec<[u8; 3]> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let pixel = self.get_pixel(x, y).unwrap();
+                [pixel.r().0, pixel.g().0, pixel.b().0]
+            })
+            .collect();
+
+        let mut boxes = if pixels.is_empty() { Vec::new() } else { vec![pixels] };
+        while boxes.len() < colors {
+            // (box index, channel, extent) of the box with the largest extent
+            let widest = boxes.iter()
+                .enumerate()
+                .flat_map(|(idx, members)| (0..3).map(move |c| (idx, c, extent(members, c))))
+                .fold(None, |best: Option<(usize, usize, u8)>, candidate| match best {
+                    Some(best) if best.2 >= candidate.2 => Some(best),
+                    _ => Some(candidate),
+                });
+            let (idx, channel) = match widest {
+                Some((idx, channel, extent)) if extent > 0 => (idx, channel),
+                _ => break,
+            };
+
+            let mut members = boxes.swap_remove(idx);
+            members.sort_by_key(|color| color[channel]);
+            // split between different values so that both halves are disjoint and non-empty
+            let median = members[members.len() / 2][channel];
+            let split = if median == members[0][channel] {
+                members.iter().position(|color| color[channel] > median).unwrap()
+            } else {
+                members.iter().position(|color| color[channel] == median).unwrap()
+            };
+            let upper = members.split_off(split);
+            boxes.push(members);
+            boxes.push(upper);
+        }
+
+        let palette: Vec<[u8; 3]> = boxes.iter()
+            .map(|members| {
+                let mut mean = [0u8; 3];
+                for (c, value) in mean.iter_mut().enumerate() {
+                    let sum: u64 = members.iter().map(|color| color[c] as u64).sum();
+                    *value = ((sum as f64) / (members.len() as f64)).round() as u8;
+                }
+                mean
+            })
+            .collect();
+
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = self.get_pixel(x, y).unwrap();
+                let color = [pixel.r().0, pixel.g().0, pixel.b().0];
+                let distance = |entry: &[u8; 3]| -> u32 {
+                    (0..3).map(|c| (entry[c] as i32 - color[c] as i32).pow(2) as u32).sum()
+                };
+                let nearest = (0..palette.len())
+                    .min_by_key(|&idx| (distance(&palette[idx]), idx))
+                    .unwrap();
+                result.set_pixel(x, y, PaletteVal::new(nearest as u8));
+            }
+        }
+
+        let palette = palette.iter()
+            .map(|c| RgbVal8U::new(ScalarVal(c[0]), ScalarVal(c[1]), ScalarVal(c[2])))
+            .collect();
+        (result, palette)
+    }
+}
+
+/// Difference between the largest and the smallest value of `channel`.
+fn extent(colors: &[[u8; 3]], channel: usize) -> u8 {
+    let min = colors.iter().map(|color| color[channel]).min().unwrap();
+    let max = colors.iter().map(|color| color[channel]).max().unwrap();
+    max - min
+}
@@ -0,0 +1,76 @@
+use {Scalar, PixelArithmetic, PixelVal};
+use {Image, ImageVal, ImageBufferVal};
+
+/// Distance of (`x`, `y`) to the image center, normalized such that the corners are at `1`.
+fn normalized_radius(x: u32, y: u32, width: u32, height: u32) -> f64 {
+    let cx = (width as f64 - 1.0) / 2.0;
+    let cy = (height as f64 - 1.0) / 2.0;
+    let max = cx.hypot(cy);
+    if max == 0.0 {
+        0.0
+    } else {
+        (x as f64 - cx).hypot(y as f64 - cy) / max
+    }
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Darkens the image towards its corners.
+    ///
+    /// Every color channel is multiplied by `max(0, 1 - strength * r^2)`, where `r` is the
+    /// distance to the image center normalized such that the corners are at `1`. The falloff is
+    /// therefore smooth and symmetric about the center, `strength = 0` returns an unchanged
+    /// copy and `strength = 1` turns the corners black. The alpha channel is left unchanged.
+    pub fn apply_vignette(&self, strength: f64) -> ImageBufferVal<ImageP::PixelT> {
+        let width = self.width();
+        let height = self.height();
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let r = normalized_radius(x, y, width, height);
+                let factor = (1.0 - strength * r * r).max(0.0);
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                    if Some(idx) == ImageP::PixelT::ALPHA_CHANNEL {
+                        continue;
+                    }
+                    let value = pixel.0.get_channel(idx).to_f64() * factor;
+                    pixel.0.set_channel(idx, Scalar::from_f64(value));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+}
+
+impl<PixelP> ImageBufferVal<PixelP>
+    where PixelP: PixelArithmetic
+{
+    /// Constructs a radial gradient test pattern.
+    ///
+    /// The color changes linearly with the distance to the image center, from `inner` at the
+    /// center to `outer` at the corners.
+    pub fn radial_gradient(width: u32,
+                           height: u32,
+                           inner: PixelVal<PixelP>,
+                           outer: PixelVal<PixelP>)
+                           -> ImageBufferVal<PixelP> {
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let r = normalized_radius(x, y, width, height);
+                let mut pixel = inner;
+                for idx in 0..PixelP::CHANNEL_COUNT {
+                    let a = inner.0.get_channel(idx).to_f64();
+                    let b = outer.0.get_channel(idx).to_f64();
+                    pixel.0.set_channel(idx, Scalar::from_f64(a + r * (b - a)));
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+use {Scalar, ScalarVal, PixelArithmetic, PixelVal, Gray32U, GrayVal32U};
+use {Image, ImageVal, ImageBufferVal, Rect};
+
+/// Statistics of a single connected component, see
+/// [`ImageVal::region_stats`](struct.ImageVal.html#method.region_stats).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RegionStat {
+    /// Label of the component, as assigned by
+    /// [`ImageVal::label_components`](struct.ImageVal.html#method.label_components).
+    pub label: u32,
+    /// Number of pixels of the component.
+    pub area: u64,
+    /// Smallest rectangle which contains all pixels of the component.
+    pub bounding_box: Rect,
+    /// Mean (`x`, `y`) coordinate of all pixels of the component.
+    pub centroid: (f64, f64),
+}
+
+/// Computes the statistics of every label of a label image, ordered by label.
+fn region_stats_of_labels(labels: &ImageBufferVal<Gray32U>) -> Vec<RegionStat> {
+    let mut stats: Vec<RegionStat> = Vec::new();
+    let mut sums: Vec<(f64, f64)> = Vec::new();
+    // (min_x, min_y, max_x, max_y) per label
+    let mut bounds: Vec<(u32, u32, u32, u32)> = Vec::new();
+    for y in 0..labels.height() {
+        for x in 0..labels.width() {
+            let label = labels.get_pixel(x, y).unwrap().intensity().0;
+            if label == 0 {
+                continue;
+            }
+            let idx = (label - 1) as usize;
+            if idx == stats.len() {
+                stats.push(RegionStat {
+                    label,
+                    area: 0,
+                    bounding_box: Rect::new(x, y, 1, 1),
+                    centroid: (0.0, 0.0),
+                });
+                sums.push((0.0, 0.0));
+                bounds.push((x, y, x, y));
+            }
+            stats[idx].area += 1;
+            sums[idx].0 += x as f64;
+            sums[idx].1 += y as f64;
+            let b = &mut bounds[idx];
+            *b = (b.0.min(x), b.1.min(y), b.2.max(x), b.3.max(y));
+        }
+    }
+
+    for ((stat, sum), b) in stats.iter_mut().zip(sums.iter()).zip(bounds.iter()) {
+        stat.centroid = (sum.0 / stat.area as f64, sum.1 / stat.area as f64);
+        stat.bounding_box = Rect::new(b.0, b.1, b.2 - b.0 + 1, b.3 - b.1 + 1);
+    }
+    stats
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Returns the (`x`, `y`) coordinates of all pixels for which `f` returns `true`.
+    ///
+    /// The coordinates are in row-major order. The image is scanned twice, first to count the
+    /// matches and then to collect them, so the result is allocated exactly once.
+    pub fn positions_where<F>(&self, f: F) -> Vec<(u32, u32)>
+        where F: Fn(PixelVal<ImageP::PixelT>) -> bool
+    {
+        let f = &f;
+        let matches = |y| (0..self.width()).filter(move |&x| f(self.get_pixel(x, y).unwrap()));
+        let count = (0..self.height()).map(|y| matches(y).count()).sum();
+        let mut result = Vec::with_capacity(count);
+        for y in 0..self.height() {
+            result.extend(matches(y).map(|x| (x, y)));
+        }
+        result
+    }
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Labels the connected components of the foreground.
+    ///
+    /// Every pixel with at least one non-zero channel belongs to the foreground. Foreground
+    /// pixels are connected to all of their 8 neighbors. The result contains `0` for background
+    /// pixels and the label of the component otherwise. Labels start at `1` and are assigned
+    /// in row-major order of the first pixel of each component.
+    pub fn label_components(&self) -> ImageBufferVal<Gray32U> {
+        let width = self.width();
+        let height = self.height();
+        let mut labels = ImageBufferVal::<Gray32U>::new_with_size(width, height);
+        let mut next_label = 1;
+        let mut queue = VecDeque::new();
+        for y in 0..height {
+            for x in 0..width {
+                if !self.is_foreground(x, y) || labels.get_pixel(x, y).unwrap().intensity().0 != 0 {
+                    continue;
+                }
+
+                let label = GrayVal32U::new(ScalarVal(next_label));
+                next_label += 1;
+                labels.set_pixel(x, y, label);
+                queue.push_back((x, y));
+                while let Some((cx, cy)) = queue.pop_front() {
+                    for ny in cy.saturating_sub(1)..(cy + 2).min(height) {
+                        for nx in cx.saturating_sub(1)..(cx + 2).min(width) {
+                            if self.is_foreground(nx, ny) &&
+                               labels.get_pixel(nx, ny).unwrap().intensity().0 == 0 {
+                                labels.set_pixel(nx, ny, label);
+                                queue.push_back((nx, ny));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        labels
+    }
+
+    /// Computes the statistics of every connected component of the foreground.
+    ///
+    /// The components are determined by
+    /// [`label_components`](#method.label_components) and the result is ordered by label.
+    pub fn region_stats(&self) -> Vec<RegionStat> {
+        region_stats_of_labels(&self.label_components())
+    }
+
+    /// Sets all connected components with less than `min_area` pixels to zero.
+    ///
+    /// The components are determined by [`label_components`](#method.label_components),
+    /// all other pixels are copied unchanged.
+    pub fn remove_small_components(&self, min_area: u64) -> ImageBufferVal<ImageP::PixelT> {
+        let labels = self.label_components();
+        let keep: Vec<bool> = region_stats_of_labels(&labels)
+            .iter()
+            .map(|stat| stat.area >= min_area)
+            .collect();
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut pixel = self.get_pixel(x, y).unwrap();
+                let label = labels.get_pixel(x, y).unwrap().intensity().0;
+                if label != 0 && !keep[(label - 1) as usize] {
+                    for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                        pixel.0.set_channel(idx, Scalar::from_f64(0.0));
+                    }
+                }
+                result.set_pixel(x, y, pixel);
+            }
+        }
+        result
+    }
+
+    fn is_foreground(&self, x: u32, y: u32) -> bool {
+        let pixel = self.get_pixel(x, y).unwrap();
+        (0..ImageP::PixelT::CHANNEL_COUNT).any(|idx| pixel.0.get_channel(idx).to_f64() != 0.0)
+    }
+}
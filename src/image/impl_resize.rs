@@ -0,0 +1,235 @@
+use std::f64::consts::PI;
+
+use {Scalar, PixelArithmetic, Rgba};
+use {Image, ImageVal, ImageBufferVal};
+use image::float_image::FloatImage;
+
+/// Interpolation filter used by [`ImageVal::resize`](struct.ImageVal.html#method.resize).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResizeFilter {
+    /// Copy the closest source pixel.
+    Nearest,
+    /// Linear interpolation between the 2x2 closest source pixels.
+    Bilinear,
+    /// Cubic (Catmull-Rom) interpolation over the 4x4 closest source pixels.
+    Bicubic,
+    /// Windowed sinc interpolation over the 6x6 closest source pixels.
+    Lanczos3,
+}
+
+impl ResizeFilter {
+    /// Half width of the filter in source pixels.
+    fn support(self) -> f64 {
+        match self {
+            ResizeFilter::Nearest => 0.5,
+            ResizeFilter::Bilinear => 1.0,
+            ResizeFilter::Bicubic => 2.0,
+            ResizeFilter::Lanczos3 => 3.0,
+        }
+    }
+
+    /// Weight of a source pixel at distance `d`.
+    fn weight(self, d: f64) -> f64 {
+        let d = d.abs();
+        match self {
+            ResizeFilter::Nearest => if d < 0.5 { 1.0 } else { 0.0 },
+            ResizeFilter::Bilinear => (1.0 - d).max(0.0),
+            ResizeFilter::Bicubic => {
+                if d < 1.0 {
+                    1.5 * d * d * d - 2.5 * d * d + 1.0
+                } else if d < 2.0 {
+                    -0.5 * d * d * d + 2.5 * d * d - 4.0 * d + 2.0
+                } else {
+                    0.0
+                }
+            }
+            ResizeFilter::Lanczos3 => {
+                if d == 0.0 {
+                    1.0
+                } else if d < 3.0 {
+                    let x = PI * d;
+                    3.0 * x.sin() * (x / 3.0).sin() / (x * x)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Maps the center of destination pixel `dst` into source coordinates.
+fn source_coord(dst: u32, src_len: u32, dst_len: u32) -> f64 {
+    (dst as f64 + 0.5) * (src_len as f64) / (dst_len as f64) - 0.5
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Resizes the image to `new_width` x `new_height` by copying the closest source pixel.
+    ///
+    /// Pixel centers are aligned, i.e. the image is scaled about its center. As no arithmetic
+    /// is involved this works for every pixel type.
+    ///
+    /// # Panics
+    /// If the image is empty but the requested size is not.
+    pub fn resize_nearest(&self,
+                          new_width: u32,
+                          new_height: u32)
+                          -> ImageBufferVal<ImageP::PixelT> {
+        let width = self.width();
+        let height = self.height();
+        assert!((width > 0 && height > 0) || new_width == 0 || new_height == 0,
+                "Can not resize an empty image to {:}x{:}",
+                new_width,
+                new_height);
+
+        let nearest = |dst, src_len, dst_len| {
+            let coord = (source_coord(dst, src_len, dst_len) + 0.5).floor();
+            coord.max(0.0).min((src_len - 1) as f64) as u32
+        };
+        let mut result = ImageBufferVal::new_with_size(new_width, new_height);
+        for y in 0..new_height {
+            let sy = nearest(y, height, new_height);
+            for x in 0..new_width {
+                let sx = nearest(x, width, new_width);
+                result.set_pixel(x, y, self.get_pixel(sx, sy).unwrap());
+            }
+        }
+        result
+    }
+}
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Resizes the image to `new_width` x `new_height` using `filter`.
+    ///
+    /// Pixel centers are aligned, i.e. the image is scaled about its center. The interpolating
+    /// filters are applied separably in `f64`, their weights are renormalized and locations
+    /// outside of the image are clamped. The filters are not widened when shrinking, so strong
+    /// downscaling may alias.
+    ///
+    /// `ResizeFilter::Nearest` is identical to [`resize_nearest`](#method.resize_nearest), which
+    /// is also available for pixel types without arithmetic.
+    ///
+    /// # Panics
+    /// If the image is empty but the requested size is not.
+    pub fn resize(&self,
+                  new_width: u32,
+                  new_height: u32,
+                  filter: ResizeFilter)
+                  -> ImageBufferVal<ImageP::PixelT> {
+        if filter == ResizeFilter::Nearest {
+            return self.resize_nearest(new_width, new_height);
+        }
+        assert!((self.width() > 0 && self.height() > 0) || new_width == 0 || new_height == 0,
+                "Can not resize an empty image to {:}x{:}",
+                new_width,
+                new_height);
+
+        FloatImage::from_image(self)
+            .resample(new_width, new_height, filter.support(), |d| filter.weight(d))
+            .to_image()
+    }
+
+    /// Resizes the image to `new_width` x `new_height` with bilinear interpolation.
+    ///
+    /// Every destination pixel interpolates between the 2x2 source pixels around its center,
+    /// locations outside of the image are clamped to the border. The interpolation is done in
+    /// `f64`, integer results are rounded to the nearest value, see
+    /// [`Scalar::from_f64`](trait.Scalar.html#tymethod.from_f64). This is a shorthand for
+    /// [`resize`](#method.resize) with `ResizeFilter::Bilinear`.
+    ///
+    /// # Panics
+    /// If the image is empty but the requested size is not.
+    pub fn resize_bilinear(&self,
+                           new_width: u32,
+                           new_height: u32)
+                           -> ImageBufferVal<ImageP::PixelT> {
+        self.resize(new_width, new_height, ResizeFilter::Bilinear)
+    }
+
+    /// Resizes the image to the size of `reference` using `filter`, e.g. to align two images
+    /// before combining them pixel by pixel.
+    ///
+    /// See [`resize`](#method.resize) for the details. If the sizes already match, the
+    /// pixels are copied unchanged regardless of `filter`.
+    ///
+    /// # Panics
+    /// If the image is empty but `reference` is not.
+    pub fn resample_like<ImageB>(&self,
+                                 reference: &ImageVal<ImageB>,
+                                 filter: ResizeFilter)
+                                 -> ImageBufferVal<ImageP::PixelT>
+        where ImageB: Image
+    {
+        let (width, height) = (reference.width(), reference.height());
+        if (width, height) == (self.width(), self.height()) {
+            self.resize_nearest(width, height)
+        } else {
+            self.resize(width, height, filter)
+        }
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Rgba<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Resizes the image like [`resize`](#method.resize), but with premultiplied alpha.
+    ///
+    /// The color channels are multiplied by the normalized alpha value before the resampling
+    /// and divided by the resampled alpha afterwards. Therefore the color of transparent pixels
+    /// does not bleed into their neighbors, which avoids dark halos along transparent edges.
+    /// Pixels which end up fully transparent are set to zero in all channels. For fully opaque
+    /// regions the result equals a plain resize.
+    ///
+    /// # Panics
+    /// If the image is empty but the requested size is not.
+    pub fn resize_premultiplied(&self,
+                                new_width: u32,
+                                new_height: u32,
+                                filter: ResizeFilter)
+                                -> ImageBufferVal<Rgba<ScalarP>> {
+        if filter == ResizeFilter::Nearest {
+            return self.resize_nearest(new_width, new_height);
+        }
+        assert!((self.width() > 0 && self.height() > 0) || new_width == 0 || new_height == 0,
+                "Can not resize an empty image to {:}x{:}",
+                new_width,
+                new_height);
+
+        let min = ScalarP::range_min().to_f64();
+        let max = ScalarP::range_max().to_f64();
+        let mut source = FloatImage::from_image(self);
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let alpha = (source.get(x, y, 3) - min) / (max - min);
+                for c in 0..3 {
+                    let value = source.get(x, y, c) * alpha;
+                    source.set(x, y, c, value);
+                }
+            }
+        }
+
+        let mut result = source.resample(new_width,
+                                          new_height,
+                                          filter.support(),
+                                          |d| filter.weight(d));
+        for y in 0..new_height {
+            for x in 0..new_width {
+                let alpha = (result.get(x, y, 3) - min) / (max - min);
+                for c in 0..4 {
+                    let value = if alpha > 0.0 {
+                        if c < 3 { result.get(x, y, c) / alpha } else { result.get(x, y, c) }
+                    } else {
+                        0.0
+                    };
+                    result.set(x, y, c, value);
+                }
+            }
+        }
+        result.to_image()
+    }
+}
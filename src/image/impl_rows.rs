@@ -0,0 +1,65 @@
+use PixelVal;
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Transforms the image scanline by scanline.
+    ///
+    /// `f` is called once per row, from top to bottom, with the decoded pixels of that row and
+    /// returns the pixels of the corresponding output row. This suits operations which need a
+    /// whole row at once, e.g. a 1D transform.
+    ///
+    /// # Panics
+    /// If `f` returns a row whose length differs from the image width.
+    pub fn map_rows<F>(&self, mut f: F) -> ImageBufferVal<ImageP::PixelT>
+        where F: FnMut(&[PixelVal<ImageP::PixelT>]) -> Vec<PixelVal<ImageP::PixelT>>
+    {
+        let width = self.width();
+        let mut row = Vec::with_capacity(width as usize);
+        let mut result = ImageBufferVal::new_with_size(width, self.height());
+        for y in 0..self.height() {
+            row.clear();
+            row.extend((0..width).map(|x| self.get_pixel(x, y).unwrap()));
+            let new_row = f(&row);
+            assert!(new_row.len() == width as usize,
+                    "Row {:} has {:} pixels instead of {:}",
+                    y,
+                    new_row.len(),
+                    width);
+            for (x, pixel) in new_row.into_iter().enumerate() {
+                result.set_pixel(x as u32, y, pixel);
+            }
+        }
+        result
+    }
+
+    /// Transforms the image column by column.
+    ///
+    /// This is the vertical counterpart of [`map_rows`](#method.map_rows): `f` is called once
+    /// per column, from left to right, with the pixels of that column from top to bottom.
+    ///
+    /// # Panics
+    /// If `f` returns a column whose length differs from the image height.
+    pub fn map_cols<F>(&self, mut f: F) -> ImageBufferVal<ImageP::PixelT>
+        where F: FnMut(&[PixelVal<ImageP::PixelT>]) -> Vec<PixelVal<ImageP::PixelT>>
+    {
+        let height = self.height();
+        let mut col = Vec::with_capacity(height as usize);
+        let mut result = ImageBufferVal::new_with_size(self.width(), height);
+        for x in 0..self.width() {
+            col.clear();
+            col.extend((0..height).map(|y| self.get_pixel(x, y).unwrap()));
+            let new_col = f(&col);
+            assert!(new_col.len() == height as usize,
+                    "Column {:} has {:} pixels instead of {:}",
+                    x,
+                    new_col.len(),
+                    height);
+            for (y, pixel) in new_col.into_iter().enumerate() {
+                result.set_pixel(x, y as u32, pixel);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,85 @@
+use PixelArithmetic;
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    /// Removes the vertical seam with the lowest energy, reducing the width by one.
+    ///
+    /// A seam is a path of one pixel per row from top to bottom, where consecutive pixels are
+    /// at most one column apart. Its energy is the sum of the
+    /// [`energy_map`](#method.energy_map) over its pixels. The globally minimal seam is found
+    /// with dynamic programming, ties are resolved towards the left.
+    ///
+    /// # Panics
+    /// If the image is less than two pixels wide.
+    pub fn remove_vertical_seam(&self) -> ImageBufferVal<ImageP::PixelT> {
+        let width = self.width();
+        let height = self.height();
+        assert!(width >= 2, "Image width ({:}) is too small to remove a seam", width);
+
+        // costs[y * width + x] is the energy of the cheapest seam from the top row to (x, y)
+        let energy = self.energy_map();
+        let mut costs: Vec<f64> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| energy.get_pixel(x, y).unwrap().intensity().0 as f64)
+            .collect();
+        let w = width as usize;
+        for y in 1..height as usize {
+            for x in 0..w {
+                let above = &costs[(y - 1) * w..y * w];
+                let best = above[x.saturating_sub(1)..(x + 2).min(w)]
+                    .iter()
+                    .cloned()
+                    .fold(f64::INFINITY, f64::min);
+                costs[y * w + x] += best;
+            }
+        }
+
+        let mut seam = vec![0usize; height as usize];
+        for y in (0..height as usize).rev() {
+            let (first, last) = if y + 1 == height as usize {
+                (0, w)
+            } else {
+                let below = seam[y + 1];
+                (below.saturating_sub(1), (below + 2).min(w))
+            };
+            let row = &costs[y * w..(y + 1) * w];
+            seam[y] = (first..last)
+                .fold(first, |best, x| if row[x] < row[best] { x } else { best });
+        }
+
+        let mut result = ImageBufferVal::new_with_size(width - 1, height);
+        for y in 0..height {
+            for x in 0..width - 1 {
+                let sx = if (x as usize) < seam[y as usize] { x } else { x + 1 };
+                result.set_pixel(x, y, self.get_pixel(sx, y).unwrap());
+            }
+        }
+        result
+    }
+
+    /// Reduces the width to `target_width` by repeatedly removing the vertical seam with the
+    /// lowest energy, see [`remove_vertical_seam`](#method.remove_vertical_seam).
+    ///
+    /// # Panics
+    /// If `target_width` is zero or larger than the width of the image.
+    pub fn seam_carve_to_width(&self, target_width: u32) -> ImageBufferVal<ImageP::PixelT> {
+        assert!(target_width > 0 && target_width <= self.width(),
+                "Invalid target width ({:}) for an image of width {:}",
+                target_width,
+                self.width());
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                result.set_pixel(x, y, self.get_pixel(x, y).unwrap());
+            }
+        }
+        while result.width() > target_width {
+            result = result.remove_vertical_seam();
+        }
+        result
+    }
+}
@@ -0,0 +1,75 @@
+use {Binary, BinaryVal};
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Binary>
+{
+    /// Thins the set pixels down to a one pixel wide skeleton (Zhang-Suen).
+    ///
+    /// Every iteration consists of two sub-passes which remove boundary pixels from the
+    /// south-east and the north-west side respectively, as long as this neither disconnects
+    /// the shape nor shortens a line end. The algorithm stops as soon as an iteration does not
+    /// change any pixel, so a connected shape results in a connected, 8-connected skeleton.
+    /// Locations outside of the image count as unset.
+    pub fn skeletonize(&self) -> ImageBufferVal<Binary> {
+        let width = self.width() as i64;
+        let height = self.height() as i64;
+        let mut set: Vec<bool> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.get_pixel(x as u32, y as u32).unwrap().value())
+            .collect();
+        let is_set = |set: &[bool], x: i64, y: i64| {
+            x >= 0 && y >= 0 && x < width && y < height && set[(y * width + x) as usize]
+        };
+
+        let mut removals = Vec::new();
+        loop {
+            let mut changed = false;
+            for &second_pass in &[false, true] {
+                removals.clear();
+                for y in 0..height {
+                    for x in 0..width {
+                        if !is_set(&set, x, y) {
+                            continue;
+                        }
+                        // neighbors P2..P9, clockwise starting north
+                        let p = [is_set(&set, x, y - 1),
+                                 is_set(&set, x + 1, y - 1),
+                                 is_set(&set, x + 1, y),
+                                 is_set(&set, x + 1, y + 1),
+                                 is_set(&set, x, y + 1),
+                                 is_set(&set, x - 1, y + 1),
+                                 is_set(&set, x - 1, y),
+                                 is_set(&set, x - 1, y - 1)];
+                        let neighbors = p.iter().filter(|&&v| v).count();
+                        let transitions = (0..8).filter(|&i| !p[i] && p[(i + 1) % 8]).count();
+                        let (n, e, s, w) = (p[0], p[2], p[4], p[6]);
+                        let outer = if second_pass {
+                            !(n && w && (e || s))
+                        } else {
+                            !(e && s && (n || w))
+                        };
+                        if (2..=6).contains(&neighbors) && transitions == 1 && outer {
+                            removals.push((y * width + x) as usize);
+                        }
+                    }
+                }
+                for &idx in &removals {
+                    set[idx] = false;
+                }
+                changed |= !removals.is_empty();
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..height {
+            for x in 0..width {
+                result.set_pixel(x as u32, y as u32, BinaryVal::new(is_set(&set, x, y)));
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,72 @@
+use {Scalar, PixelArithmetic};
+use {Image, ImageVal, ImageBufferVal};
+
+/// Averages a stack of images pixel by pixel.
+///
+/// Every channel is accumulated as `f64` and converted back with
+/// [`Scalar::from_f64`](trait.Scalar.html#tymethod.from_f64). Averaging `n` frames with
+/// independent noise reduces its standard deviation by `sqrt(n)`.
+///
+/// # Panics
+/// If `images` is empty or the sizes of the images differ.
+pub fn stack_mean<ImageP>(images: &[ImageVal<ImageP>]) -> ImageBufferVal<ImageP::PixelT>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    combine_stack(images, |values| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Computes the median of a stack of images pixel by pixel.
+///
+/// Every channel is handled separately. For an even number of images the result is the
+/// mean of the two middle values. In contrast to [`stack_mean`](fn.stack_mean.html)
+/// outliers which occur in less than half of the frames, e.g. a passing object, are
+/// removed completely.
+///
+/// # Panics
+/// If `images` is empty, the sizes of the images differ or the stack contains values which can
+/// not be ordered, i.e. floating point NaN.
+pub fn stack_median<ImageP>(images: &[ImageVal<ImageP>]) -> ImageBufferVal<ImageP::PixelT>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic
+{
+    combine_stack(images, |values| {
+        values.sort_by(|a, b| a.partial_cmp(b).expect("Can not order NaN values"));
+        let mid = values.len() / 2;
+        if values.len() % 2 == 0 {
+            (values[mid - 1] + values[mid]) / 2.0
+        } else {
+            values[mid]
+        }
+    })
+}
+
+/// Combines the values of every channel across the stack with `f`.
+fn combine_stack<ImageP, F>(images: &[ImageVal<ImageP>],
+                            mut f: F)
+                            -> ImageBufferVal<ImageP::PixelT>
+    where ImageP: Image,
+          ImageP::PixelT: PixelArithmetic,
+          F: FnMut(&mut [f64]) -> f64
+{
+    assert!(!images.is_empty(), "Image stack is empty");
+    let (width, height) = (images[0].width(), images[0].height());
+    assert!(images.iter().all(|image| (image.width(), image.height()) == (width, height)),
+            "Sizes of the images in the stack differ");
+
+    let mut values = vec![0f64; images.len()];
+    let mut result = ImageBufferVal::new_with_size(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let mut pixel = images[0].get_pixel(x, y).unwrap();
+            for idx in 0..ImageP::PixelT::CHANNEL_COUNT {
+                for (value, image) in values.iter_mut().zip(images.iter()) {
+                    *value = image.get_pixel(x, y).unwrap().0.get_channel(idx).to_f64();
+                }
+                pixel.0.set_channel(idx, Scalar::from_f64(f(&mut values)));
+            }
+            result.set_pixel(x, y, pixel);
+        }
+    }
+    result
+}
@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+use {Scalar, ScalarVal, Gray, GrayVal, Gray8U, Binary, BinaryVal};
+use {Image, ImageVal, ImageBufferVal};
+use image::integral::IntegralImage;
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray8U>
+{
+    /// Binarizes the image with Sauvola's local adaptive threshold.
+    ///
+    /// Every pixel is compared against `mean * (1 + k * (stddev / r - 1))`, where `mean` and
+    /// `stddev` are computed over the `window x window` neighborhood centered at the pixel.
+    /// Windows reaching over the border are clamped to the image. Pixels brighter than their
+    /// threshold are set in the result, so dark text on bright paper ends up unset.
+    ///
+    /// Typical parameters are `k = 0.2` and `r = 128` (half of the intensity range), with a
+    /// window somewhat larger than the strokes of the text.
+    ///
+    /// # Panics
+    /// If `window` is zero.
+    pub fn sauvola(&self, window: u32, k: f64, r: f64) -> ImageBufferVal<Binary> {
+        assert!(window > 0, "Window size must not be zero");
+
+        let width = self.width();
+        let height = self.height();
+        let value = |x, y| self.get_pixel(x, y).unwrap().intensity().0 as f64;
+        let sums = IntegralImage::new(width, height, value);
+        let squares = IntegralImage::new(width, height, |x, y| value(x, y) * value(x, y));
+
+        let before = (window - 1) / 2;
+        let after = window / 2;
+        let mut result = ImageBufferVal::<Binary>::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let (x0, y0) = (x.saturating_sub(before), y.saturating_sub(before));
+                let (x1, y1) = ((x + after + 1).min(width), (y + after + 1).min(height));
+                let count = ((x1 - x0) * (y1 - y0)) as f64;
+                let mean = sums.sum(x0, y0, x1, y1) / count;
+                let variance = (squares.sum(x0, y0, x1, y1) / count - mean * mean).max(0.0);
+                let threshold = mean * (1.0 + k * (variance.sqrt() / r - 1.0));
+                result.set_pixel(x, y, BinaryVal::new(value(x, y) > threshold));
+            }
+        }
+        result
+    }
+}
+
+impl<ImageP, ScalarP> ImageVal<ImageP>
+    where ImageP: Image<PixelT = Gray<ScalarP>>,
+          ScalarP: Scalar
+{
+    /// Sets every pixel with an intensity above `t` to the upper end of the
+    /// [scalar range](trait.Scalar.html#tymethod.range_max) and all others to the lower end.
+    ///
+    /// For `Gray8U` the result only contains 255 and 0, see
+    /// [`threshold_to`](#method.threshold_to) for other output values.
+    pub fn threshold(&self, t: ScalarVal<ScalarP>) -> ImageBufferVal<Gray<ScalarP>> {
+        self.threshold_to(t, ScalarVal(ScalarP::range_max()), ScalarVal(ScalarP::range_min()))
+    }
+
+    /// Sets every pixel with an intensity above `t` to `high` and all others to `low`.
+    pub fn threshold_to(&self,
+                        t: ScalarVal<ScalarP>,
+                        high: ScalarVal<ScalarP>,
+                        low: ScalarVal<ScalarP>)
+                        -> ImageBufferVal<Gray<ScalarP>> {
+        let mut result = ImageBufferVal::new_with_size(self.width(), self.height());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let above = self.get_pixel(x, y).unwrap().intensity() > t;
+                result.set_pixel(x, y, GrayVal::new(if above { high } else { low }));
+            }
+        }
+        result
+    }
+
+    /// Binarizes the image with a hysteresis threshold.
+    ///
+    /// Pixels with an intensity above `high` are strong and always set. Pixels with an
+    /// intensity above `low` are weak and only set if they are connected to a strong pixel,
+    /// either directly or through a chain of other weak pixels (8-connectivity). All other
+    /// pixels are unset.
+    pub fn hysteresis(&self,
+                      low: ScalarVal<ScalarP>,
+                      high: ScalarVal<ScalarP>)
+                      -> ImageBufferVal<Binary> {
+        let width = self.width();
+        let height = self.height();
+        let value = |x, y| self.get_pixel(x, y).unwrap().intensity().0.to_f64();
+        let (low, high) = (low.0.to_f64(), high.0.to_f64());
+
+        let mut result = ImageBufferVal::<Binary>::new_with_size(width, height);
+        let mut queue = VecDeque::new();
+        for y in 0..height {
+            for x in 0..width {
+                if value(x, y) > high {
+                    result.set_pixel(x, y, BinaryVal::new(true));
+                    queue.push_back((x, y));
+                }
+            }
+        }
+        while let Some((cx, cy)) = queue.pop_front() {
+            for ny in cy.saturating_sub(1)..(cy + 2).min(height) {
+                for nx in cx.saturating_sub(1)..(cx + 2).min(width) {
+                    if !result.get_pixel(nx, ny).unwrap().value() && value(nx, ny) > low {
+                        result.set_pixel(nx, ny, BinaryVal::new(true));
+                        queue.push_back((nx, ny));
+                    }
+                }
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,77 @@
+use {Image, ImageVal, ImageBufferVal};
+
+impl<ImageP> ImageVal<ImageP>
+    where ImageP: Image
+{
+    /// Copies the `width` x `height` region whose top left corner is at (`x`, `y`).
+    ///
+    /// Returns `None` if the region does not fit into the image. A region with zero width or
+    /// height is valid as long as its corner lies within or on the border of the image, the
+    /// result is an empty image in this case.
+    pub fn crop(&self,
+                x: u32,
+                y: u32,
+                width: u32,
+                height: u32)
+                -> Option<ImageBufferVal<ImageP::PixelT>> {
+        if x.checked_add(width)? > self.width() || y.checked_add(height)? > self.height() {
+            return None;
+        }
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for dy in 0..height {
+            for dx in 0..width {
+                result.set_pixel(dx, dy, self.get_pixel(x + dx, y + dy).unwrap());
+            }
+        }
+        Some(result)
+    }
+
+    /// Mirrors the image at its vertical axis, i.e. reverses the order of the columns.
+    ///
+    /// The result has the same size and pitch as this image.
+    pub fn flip_horizontal(&self) -> ImageBufferVal<ImageP::PixelT> {
+        let width = self.width();
+        let mut result =
+            ImageBufferVal::new_with_size_and_pitch(width, self.height(), self.pitch());
+        for y in 0..self.height() {
+            for x in 0..width {
+                result.set_pixel(width - 1 - x, y, self.get_pixel(x, y).unwrap());
+            }
+        }
+        result
+    }
+
+    /// Mirrors the image at its horizontal axis, i.e. reverses the order of the rows.
+    ///
+    /// The result has the same size and pitch as this image. As the rows stay intact, they
+    /// are copied as a whole.
+    pub fn flip_vertical(&self) -> ImageBufferVal<ImageP::PixelT> {
+        let height = self.height() as usize;
+        let pitch = self.pitch() as usize;
+        let mut raw_data = vec![0; self.get_size_in_bytes()];
+        self.write_into_raw_buffer(&mut raw_data);
+
+        let mut result =
+            ImageBufferVal::new_with_size_and_pitch(self.width(), self.height(), self.pitch());
+        for (y, row) in result.as_bytes_mut().chunks_exact_mut(pitch.max(1)).enumerate() {
+            let src = (height - 1 - y) * pitch;
+            row.copy_from_slice(&raw_data[src..src + pitch]);
+        }
+        result
+    }
+
+    /// Swaps rows and columns, the pixel at (`x`, `y`) of the result is the pixel at (`y`, `x`)
+    /// of this image.
+    ///
+    /// The result is `height` x `width` with the minimum pitch. Together with a horizontal
+    /// operation this allows to implement the vertical one, e.g. for separable filters.
+    pub fn transpose(&self) -> ImageBufferVal<ImageP::PixelT> {
+        let mut result = ImageBufferVal::new_with_size(self.height(), self.width());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                result.set_pixel(y, x, self.get_pixel(x, y).unwrap());
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,30 @@
+/// Summed area table which allows to compute the sum over any rectangle in constant time.
+pub(crate) struct IntegralImage {
+    width: u32,
+    sums: Vec<f64>,
+}
+
+impl IntegralImage {
+    /// Builds the table for an image of the given size whose values are returned by `f`.
+    pub(crate) fn new<F>(width: u32, height: u32, f: F) -> IntegralImage
+        where F: Fn(u32, u32) -> f64
+    {
+        let stride = width as usize + 1;
+        let mut sums = vec![0.0; stride * (height as usize + 1)];
+        for y in 0..height as usize {
+            let mut row_sum = 0.0;
+            for x in 0..width as usize {
+                row_sum += f(x as u32, y as u32);
+                sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + row_sum;
+            }
+        }
+        IntegralImage { width, sums }
+    }
+
+    /// Returns the sum over the pixels from (`x0`, `y0`) up to but excluding (`x1`, `y1`).
+    pub(crate) fn sum(&self, x0: u32, y0: u32, x1: u32, y1: u32) -> f64 {
+        let stride = self.width as usize + 1;
+        let at = |x: u32, y: u32| self.sums[y as usize * stride + x as usize];
+        at(x1, y1) - at(x0, y1) - at(x1, y0) + at(x0, y0)
+    }
+}
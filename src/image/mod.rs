@@ -1,10 +1,58 @@
 mod generics;
+mod border;
+mod rect;
+mod float_image;
+mod integral;
+mod palette_image;
+mod fft;
+mod rng;
+mod font;
 mod impl_core;
 mod impl_buffer;
+mod impl_filter;
+mod impl_point;
+mod impl_metric;
+mod impl_geometry;
+mod impl_histogram;
+mod impl_threshold;
+mod impl_float;
+mod impl_resize;
+mod impl_encode;
+mod impl_rows;
+mod impl_morphology;
+mod impl_skeleton;
+mod impl_channels;
+mod impl_radial;
+mod impl_draw;
+mod impl_fft;
+mod impl_hough;
+mod impl_blend;
+mod impl_seam;
+mod impl_noise;
+mod impl_aspect;
+mod impl_moments;
+mod impl_stack;
+mod impl_bayer;
+mod impl_contact;
+mod impl_diff;
+mod impl_quantize;
+mod impl_map;
+mod impl_transform;
+#[cfg(feature = "ndarray")]
+mod impl_ndarray;
+mod impl_region;
 
 pub use self::generics::*;
+pub use self::border::*;
+pub use self::rect::*;
+pub use self::palette_image::*;
 pub use self::impl_core::*;
 pub use self::impl_buffer::*;
+pub use self::impl_region::*;
+pub use self::impl_moments::*;
+pub use self::impl_resize::ResizeFilter;
+pub use self::impl_bayer::BayerPattern;
+pub use self::impl_stack::{stack_mean, stack_median};
 
 #[test]
 fn test_image_buffer() {
@@ -23,4 +71,2600 @@ fn test_image_buffer() {
     let mut raw_buffer = [0u8; 4];
     img.write_into_raw_buffer(&mut raw_buffer);
     assert_eq!(raw_buffer, [0u8, 1, 2, 3]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_get_pixel_bounds() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(3, 2);
+    img.set_pixel(2, 1, GrayVal8U::new(ScalarVal(7)));
+    for &(x, y) in &[(0, 0), (2, 0), (0, 1)] {
+        assert_eq!(img.get_pixel(x, y), Some(GrayVal8U::new(ScalarVal(0))));
+    }
+    assert_eq!(img.get_pixel(2, 1), Some(GrayVal8U::new(ScalarVal(7))));
+    assert_eq!(img.get_pixel(3, 0), None);
+    assert_eq!(img.get_pixel(0, 2), None);
+    assert_eq!(img.get_pixel(3, 2), None);
+    assert_eq!(img.get_pixel(999, 0), None);
+}
+
+#[test]
+fn test_raw_buffer_endianness() {
+    use {ScalarVal, Gray32U, GrayVal32U, Endianness};
+
+    let mut img = ImageBufferVal::<Gray32U>::new_with_size_and_pitch(2, 1, 10);
+    img.set_pixel(0, 0, GrayVal32U::new(ScalarVal(0x01020304)));
+    img.set_pixel(1, 0, GrayVal32U::new(ScalarVal(0xa0b0c0d0)));
+
+    let mut little = [0u8; 10];
+    img.write_into_raw_buffer_with_endianness(&mut little, Endianness::Little);
+    assert_eq!(little, [4, 3, 2, 1, 0xd0, 0xc0, 0xb0, 0xa0, 0, 0]);
+    let mut big = [0u8; 10];
+    img.write_into_raw_buffer_with_endianness(&mut big, Endianness::Big);
+    assert_eq!(big, [1, 2, 3, 4, 0xa0, 0xb0, 0xc0, 0xd0, 0, 0]);
+
+    for &(buffer, endianness) in &[(little, Endianness::Little), (big, Endianness::Big)] {
+        let mut loaded = ImageBufferVal::<Gray32U>::new_with_size_and_pitch(2, 1, 10);
+        loaded.load_from_raw_buffer_with_endianness(&buffer, endianness);
+        assert_eq!(loaded.get_pixel(0, 0), img.get_pixel(0, 0));
+        assert_eq!(loaded.get_pixel(1, 0), img.get_pixel(1, 0));
+    }
+
+    let mut native = [0u8; 10];
+    img.write_into_raw_buffer_with_endianness(&mut native, Endianness::Native);
+    let mut expected = [0u8; 10];
+    img.write_into_raw_buffer(&mut expected);
+    assert_eq!(native, expected);
+}
+
+#[test]
+fn test_signed_difference() {
+    use {ScalarVal, Gray16I, GrayVal16I};
+
+    let mut a = ImageBufferVal::<Gray16I>::new_with_size(3, 1);
+    let mut b = ImageBufferVal::<Gray16I>::new_with_size(3, 1);
+    for x in 0..3 {
+        a.set_pixel(x, 0, GrayVal16I::new(ScalarVal(100 * x as i16)));
+        b.set_pixel(x, 0, GrayVal16I::new(ScalarVal(-1000 + 1000 * x as i16)));
+    }
+    let diff = &a - &b;
+    let values: Vec<i16> = (0..3).map(|x| diff.get_pixel(x, 0).unwrap().intensity().0).collect();
+    assert_eq!(values, vec![1000, 100, -800]);
+
+    let reversed = &b - &a;
+    assert_eq!(reversed.get_pixel(1, 0).unwrap().intensity(), ScalarVal(-100));
+}
+
+#[test]
+#[should_panic(expected = "Pixel (3, 0) is outside of the 3x2 image")]
+fn test_set_pixel_right_of_image() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(3, 2);
+    img.set_pixel(3, 0, GrayVal8U::new(ScalarVal(1)));
+}
+
+#[test]
+#[should_panic(expected = "Pixel (0, 2) is outside of the 3x2 image")]
+fn test_set_pixel_below_image() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(3, 2);
+    img.set_pixel(0, 2, GrayVal8U::new(ScalarVal(1)));
+}
+
+#[test]
+fn test_clone_into() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut src = ImageBufferVal::<Gray8U>::new_with_size(3, 2);
+    for y in 0..2 {
+        for x in 0..3 {
+            src.set_pixel(x, y, GrayVal8U::new(ScalarVal((y * 3 + x) as u8)));
+        }
+    }
+
+    let mut dst = ImageBufferVal::<Gray8U>::new_with_size(5, 5);
+    src.clone_into(&mut dst);
+    assert_eq!(dst.width(), 3);
+    assert_eq!(dst.height(), 2);
+    assert_eq!(dst.pitch(), src.pitch());
+    for y in 0..2 {
+        for x in 0..3 {
+            assert_eq!(dst.get_pixel(x, y), src.get_pixel(x, y));
+        }
+    }
+}
+
+#[test]
+fn test_mean_filter() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    let mut img = ImageBufferVal::<Gray32F>::new_with_size(5, 3);
+    img.set_pixel(2, 1, GrayVal32F::new(ScalarVal(3.0)));
+
+    let identity = img.mean_filter(1, 1);
+    for y in 0..3 {
+        for x in 0..5 {
+            assert_eq!(identity.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+
+    let smoothed = img.mean_filter(3, 1);
+    for y in 0..3 {
+        for x in 0..5 {
+            let expected = if y == 1 && (1..4).contains(&x) { 1.0 } else { 0.0 };
+            assert_eq!(smoothed.get_pixel(x, y).unwrap(),
+                       GrayVal32F::new(ScalarVal(expected)));
+        }
+    }
+}
+
+#[test]
+fn test_quantize() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut ramp = ImageBufferVal::<Gray8U>::new_with_size(256, 1);
+    for x in 0..256 {
+        ramp.set_pixel(x, 0, GrayVal8U::new(ScalarVal(x as u8)));
+    }
+
+    let quantized = ramp.quantize(4);
+    for x in 0..256 {
+        let value = quantized.get_pixel(x, 0).unwrap().intensity().0;
+        assert!([0, 85, 170, 255].contains(&value));
+    }
+    assert_eq!(quantized.get_pixel(42, 0).unwrap().intensity(), ScalarVal(0));
+    assert_eq!(quantized.get_pixel(43, 0).unwrap().intensity(), ScalarVal(85));
+
+    let thresholded = ramp.quantize(2);
+    assert_eq!(thresholded.get_pixel(127, 0).unwrap().intensity(), ScalarVal(0));
+    assert_eq!(thresholded.get_pixel(128, 0).unwrap().intensity(), ScalarVal(255));
+
+    let identity = ramp.quantize(256);
+    for x in 0..256 {
+        assert_eq!(identity.get_pixel(x, 0), ramp.get_pixel(x, 0));
+    }
+}
+
+#[test]
+fn test_correlate_and_convolve() {
+    use {ScalarVal, Gray32F, GrayVal32F, Kernel};
+
+    let mut img = ImageBufferVal::<Gray32F>::new_with_size(5, 5);
+    img.set_pixel(2, 2, GrayVal32F::new(ScalarVal(1.0)));
+    let one = GrayVal32F::new(ScalarVal(1.0));
+
+    // asymmetric kernel picking the right neighbor
+    let zero = ScalarVal(0.0f32);
+    let asymmetric = Kernel::new(3, 3, &[zero, zero, zero,
+                                         zero, zero, ScalarVal(1.0),
+                                         zero, zero, zero]);
+    let correlated = img.correlate(&asymmetric, BorderMode::Zero);
+    let convolved = img.convolve(&asymmetric, BorderMode::Zero);
+    assert_eq!(correlated.get_pixel(1, 2).unwrap(), one);
+    assert_eq!(correlated.get_pixel(3, 2).unwrap().intensity(), ScalarVal(0.0));
+    assert_eq!(convolved.get_pixel(3, 2).unwrap(), one);
+    assert_eq!(convolved.get_pixel(1, 2).unwrap().intensity(), ScalarVal(0.0));
+
+    // symmetric kernel
+    let symmetric = Kernel::new(3, 3, &[zero, ScalarVal(1.0), zero,
+                                        ScalarVal(2.0), ScalarVal(4.0), ScalarVal(2.0),
+                                        zero, ScalarVal(1.0), zero]);
+    let correlated = img.correlate(&symmetric, BorderMode::Clamp);
+    let convolved = img.convolve(&symmetric, BorderMode::Clamp);
+    for y in 0..5 {
+        for x in 0..5 {
+            assert_eq!(correlated.get_pixel(x, y), convolved.get_pixel(x, y));
+        }
+    }
+    assert_eq!(convolved.get_pixel(1, 2).unwrap().intensity(), ScalarVal(2.0));
+}
+
+#[test]
+fn test_unsharp_mask() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(10, 3);
+    for y in 0..3 {
+        for x in 0..10 {
+            let value = if x < 5 { 50 } else { 200 };
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal(value)));
+        }
+    }
+
+    let identity = img.unsharp_mask(1.0, 0.0);
+    for y in 0..3 {
+        for x in 0..10 {
+            assert_eq!(identity.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+
+    let sharpened = img.unsharp_mask(1.0, 1.0);
+    assert!(sharpened.get_pixel(4, 1).unwrap().intensity().0 < 50);
+    assert!(sharpened.get_pixel(5, 1).unwrap().intensity().0 > 200);
+    assert_eq!(sharpened.get_pixel(0, 1).unwrap().intensity(), ScalarVal(50));
+    assert_eq!(sharpened.get_pixel(9, 1).unwrap().intensity(), ScalarVal(200));
+
+    let oversharpened = img.unsharp_mask(1.0, 10.0);
+    assert_eq!(oversharpened.get_pixel(4, 1).unwrap().intensity(), ScalarVal(0));
+    assert_eq!(oversharpened.get_pixel(5, 1).unwrap().intensity(), ScalarVal(255));
+}
+
+#[test]
+fn test_bilateral() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(12, 5);
+    for y in 0..5 {
+        for x in 0..12 {
+            let base = if x < 6 { 50 } else { 200 };
+            let noise = ((x * 7 + y * 13) % 11) as i32 - 5;
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal((base + noise) as u8)));
+        }
+    }
+
+    let filtered = img.bilateral(2.0, 20.0, 3);
+    let intensity = |image: &ImageBufferVal<Gray8U>, x, y| {
+        image.get_pixel(x, y).unwrap().intensity().0 as f64
+    };
+
+    // the edge stays sharp
+    for y in 0..5 {
+        assert!((intensity(&filtered, 5, y) - 50.0).abs() <= 5.0);
+        assert!((intensity(&filtered, 6, y) - 200.0).abs() <= 5.0);
+    }
+
+    // the flat regions get smoothed
+    let deviation = |image: &ImageBufferVal<Gray8U>| {
+        let mut sum = 0.0;
+        for y in 0..5 {
+            for x in 0..5 {
+                sum += (intensity(image, x, y) - 50.0).abs();
+            }
+        }
+        sum
+    };
+    assert!(deviation(&filtered) < deviation(&img) / 2.0);
+
+    // a very large range sigma degrades to a Gaussian blur with the same window
+    let blurred = img.bilateral(2.0, 1e9, 6);
+    let gaussian = img.gaussian_blur(2.0);
+    for y in 0..5 {
+        for x in 0..12 {
+            assert!((intensity(&blurred, x, y) - intensity(&gaussian, x, y)).abs() <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_srgb_transfer() {
+    use {ScalarVal, Gray8U, GrayVal8U, Gray64F, GrayVal64F};
+
+    let values = [0.0, 0.02, 0.04045, 0.2, 0.5, 0.8, 1.0];
+    let mut img = ImageBufferVal::<Gray64F>::new_with_size(values.len() as u32, 1);
+    for (x, value) in values.iter().enumerate() {
+        img.set_pixel(x as u32, 0, GrayVal64F::new(ScalarVal(*value)));
+    }
+
+    let linear = img.srgb_to_linear();
+    // linear segment near black
+    assert!((linear.get_pixel(1, 0).unwrap().intensity().0 - 0.02 / 12.92).abs() < 1e-12);
+    // power curve
+    assert!((linear.get_pixel(4, 0).unwrap().intensity().0 - 0.214041).abs() < 1e-6);
+
+    let round_trip = linear.linear_to_srgb();
+    for x in 0..values.len() as u32 {
+        let expected = img.get_pixel(x, 0).unwrap().intensity().0;
+        assert!((round_trip.get_pixel(x, 0).unwrap().intensity().0 - expected).abs() < 1e-6);
+    }
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(3, 1);
+    img.set_pixel(1, 0, GrayVal8U::new(ScalarVal(128)));
+    img.set_pixel(2, 0, GrayVal8U::new(ScalarVal(255)));
+    let linear = img.srgb_to_linear();
+    assert_eq!(linear.get_pixel(0, 0).unwrap().intensity(), ScalarVal(0));
+    assert_eq!(linear.get_pixel(1, 0).unwrap().intensity(), ScalarVal(55));
+    assert_eq!(linear.get_pixel(2, 0).unwrap().intensity(), ScalarVal(255));
+    assert_eq!(linear.linear_to_srgb().get_pixel(1, 0).unwrap().intensity(), ScalarVal(128));
+}
+
+#[test]
+fn test_srgb_transfer_keeps_alpha() {
+    use {ScalarVal, Rgba8U, RgbaVal8U};
+
+    let mut img = ImageBufferVal::<Rgba8U>::new_with_size(2, 1);
+    let gray = ScalarVal(128);
+    img.set_pixel(0, 0, RgbaVal8U::new(gray, ScalarVal(0), ScalarVal(255), ScalarVal(128)));
+    img.set_pixel(1, 0, RgbaVal8U::new(gray, gray, gray, ScalarVal(77)));
+
+    let linear = img.srgb_to_linear();
+    assert_eq!(linear.get_pixel(0, 0),
+               Some(RgbaVal8U::new(ScalarVal(55), ScalarVal(0), ScalarVal(255), ScalarVal(128))));
+    assert_eq!(linear.get_pixel(1, 0).unwrap().a(), ScalarVal(77));
+
+    let srgb = linear.linear_to_srgb();
+    assert_eq!(srgb.get_pixel(0, 0), img.get_pixel(0, 0));
+    assert_eq!(srgb.get_pixel(1, 0), img.get_pixel(1, 0));
+}
+
+#[test]
+fn test_region_stats() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut mask = ImageBufferVal::<Gray8U>::new_with_size(8, 6);
+    let on = GrayVal8U::new(ScalarVal(255));
+    // 3x2 blob at (1, 1)
+    for y in 1..3 {
+        for x in 1..4 {
+            mask.set_pixel(x, y, on);
+        }
+    }
+    // diagonal blob at (5, 3), (6, 4), (7, 5)
+    for i in 0..3 {
+        mask.set_pixel(5 + i, 3 + i, on);
+    }
+
+    let stats = mask.region_stats();
+    assert_eq!(stats.len(), 2);
+
+    assert_eq!(stats[0].label, 1);
+    assert_eq!(stats[0].area, 6);
+    assert_eq!(stats[0].bounding_box, Rect::new(1, 1, 3, 2));
+    assert_eq!(stats[0].centroid, (2.0, 1.5));
+
+    assert_eq!(stats[1].label, 2);
+    assert_eq!(stats[1].area, 3);
+    assert_eq!(stats[1].bounding_box, Rect::new(5, 3, 3, 3));
+    assert_eq!(stats[1].centroid, (6.0, 4.0));
+}
+
+#[test]
+fn test_remove_small_components() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut mask = ImageBufferVal::<Gray8U>::new_with_size(8, 6);
+    let on = GrayVal8U::new(ScalarVal(255));
+    for y in 0..4 {
+        for x in 0..4 {
+            mask.set_pixel(x, y, on);
+        }
+    }
+    mask.set_pixel(6, 4, on);
+    mask.set_pixel(7, 5, on);
+
+    let cleaned = mask.remove_small_components(3);
+    for y in 0..6 {
+        for x in 0..8 {
+            let expected = if x < 4 && y < 4 { 255 } else { 0 };
+            assert_eq!(cleaned.get_pixel(x, y).unwrap().intensity(), ScalarVal(expected));
+        }
+    }
+    assert_eq!(cleaned.region_stats().len(), 1);
+}
+
+#[test]
+fn test_ssim() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(16, 16);
+    for y in 0..16 {
+        for x in 0..16 {
+            let value = if (x / 2 + y / 3) % 2 == 0 { 30 } else { 220 };
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal(value)));
+        }
+    }
+
+    assert_eq!(img.ssim(&img, 7), 1.0);
+
+    let blurred = img.gaussian_blur(1.5);
+    let similarity = img.ssim(&blurred, 7);
+    assert!(similarity > 0.0 && similarity < 1.0);
+}
+
+#[test]
+fn test_warp_perspective() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(4, 3);
+    for y in 0..3 {
+        for x in 0..4 {
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal((10 * y + x) as u8)));
+        }
+    }
+
+    let identity = img.warp_perspective([1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+                                        4,
+                                        3,
+                                        BorderMode::Zero);
+    for y in 0..3 {
+        for x in 0..4 {
+            assert_eq!(identity.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+
+    // the divisor 1 + x / 2 shrinks the coordinates of the right part of the image
+    let skewed = img.warp_perspective([1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.5, 0.0, 1.0],
+                                      3,
+                                      3,
+                                      BorderMode::Zero);
+    assert_eq!(skewed.get_pixel(0, 1), img.get_pixel(0, 1));
+    assert_eq!(skewed.get_pixel(2, 0), img.get_pixel(1, 0));
+    assert_eq!(skewed.get_pixel(2, 2), img.get_pixel(1, 1));
+
+    // w' = 0 everywhere: (0, 0) maps to NaN and all other locations to positive infinity
+    let degenerate = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    let clamped = img.warp_perspective(degenerate, 2, 2, BorderMode::Clamp);
+    assert_eq!(clamped.get_pixel(0, 0), img.get_pixel(0, 0));
+    assert_eq!(clamped.get_pixel(1, 0), img.get_pixel(3, 0));
+    assert_eq!(clamped.get_pixel(0, 1), img.get_pixel(0, 2));
+    assert_eq!(clamped.get_pixel(1, 1), img.get_pixel(3, 2));
+    let zero = img.warp_perspective(degenerate, 2, 2, BorderMode::Zero);
+    for y in 0..2 {
+        for x in 0..2 {
+            assert_eq!(zero.get_pixel(x, y).unwrap().intensity(), ScalarVal(0));
+        }
+    }
+
+    // huge and infinite coordinates do not overflow
+    for &w in &[1.0, 1e-300] {
+        let huge = img.warp_perspective([1e300, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, w],
+                                        2,
+                                        1,
+                                        BorderMode::Clamp);
+        assert_eq!(huge.get_pixel(1, 0), img.get_pixel(3, 0));
+    }
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_ndarray_round_trip() {
+    use {ScalarVal, Gray16U, GrayVal16U};
+
+    let mut img = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(3, 2, 10);
+    for y in 0..2 {
+        for x in 0..3 {
+            img.set_pixel(x, y, GrayVal16U::new(ScalarVal((1000 * y + x) as u16)));
+        }
+    }
+
+    let array = img.to_ndarray();
+    assert_eq!(array.dim(), (2, 3, 1));
+    assert_eq!(array[[1, 2, 0]], 1002);
+
+    let round_trip = ImageBufferVal::<Gray16U>::from_ndarray(&array).unwrap();
+    assert_eq!(round_trip.width(), 3);
+    assert_eq!(round_trip.height(), 2);
+    for y in 0..2 {
+        for x in 0..3 {
+            assert_eq!(round_trip.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+
+    let wrong_channels = ::ndarray::Array3::<u16>::zeros((2, 3, 2));
+    assert!(ImageBufferVal::<Gray16U>::from_ndarray(&wrong_channels).is_none());
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_ndarray_round_trip_rgb() {
+    use {ScalarVal, Rgb8U, RgbVal8U};
+
+    // 2 pixels need 6 bytes per row, the pitch adds 2 bytes of padding
+    let mut img = ImageBufferVal::<Rgb8U>::new_with_size_and_pitch(2, 3, 8);
+    for y in 0..3 {
+        for x in 0..2 {
+            let base = (10 * y + 3 * x) as u8;
+            let pixel = RgbVal8U::new(ScalarVal(base), ScalarVal(base + 1), ScalarVal(base + 2));
+            img.set_pixel(x, y, pixel);
+        }
+    }
+
+    let array = img.to_ndarray();
+    assert_eq!(array.dim(), (3, 2, 3));
+    // the last axis holds the channels in red, green, blue order
+    assert_eq!(array[[2, 1, 0]], 23);
+    assert_eq!(array[[2, 1, 1]], 24);
+    assert_eq!(array[[2, 1, 2]], 25);
+    assert_eq!(array[[1, 0, 2]], 12);
+
+    let round_trip = ImageBufferVal::<Rgb8U>::from_ndarray(&array).unwrap();
+    assert_eq!((round_trip.width(), round_trip.height(), round_trip.pitch()), (2, 3, 6));
+    for y in 0..3 {
+        for x in 0..2 {
+            assert_eq!(round_trip.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+}
+
+#[test]
+fn test_sample_patch() {
+    use std::f64::consts::PI;
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(7, 7);
+    for y in 0..7 {
+        for x in 0..7 {
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal((10 * y + x) as u8)));
+        }
+    }
+
+    let patch = img.sample_patch((3.0, 4.0), 3, 0.0);
+    assert_eq!(patch.width(), 3);
+    assert_eq!(patch.height(), 3);
+    for j in 0..3 {
+        for i in 0..3 {
+            assert_eq!(patch.get_pixel(i, j), img.get_pixel(i + 2, j + 3));
+        }
+    }
+
+    // rotating by 90 degrees maps the patch x axis onto the image y axis
+    let rotated = img.sample_patch((3.0, 4.0), 3, PI / 2.0);
+    assert_eq!(rotated.get_pixel(0, 1), img.get_pixel(3, 3));
+    assert_eq!(rotated.get_pixel(2, 1), img.get_pixel(3, 5));
+    assert_eq!(rotated.get_pixel(1, 0), img.get_pixel(4, 4));
+
+    // out of bounds samples are clamped
+    let border = img.sample_patch((0.0, 0.0), 3, 0.0);
+    assert_eq!(border.get_pixel(0, 0), img.get_pixel(0, 0));
+    assert_eq!(border.get_pixel(2, 0), img.get_pixel(1, 0));
+}
+
+#[test]
+fn test_sauvola() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    // text-like vertical strokes under a strong horizontal lighting gradient
+    let is_text = |x: u32, y: u32| (8..24).contains(&y) && x % 8 < 2;
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(64, 32);
+    for y in 0..32 {
+        for x in 0..64 {
+            let paper = 60.0 + 3.0 * x as f64;
+            let value = if is_text(x, y) { paper * 0.4 } else { paper };
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal(value as u8)));
+        }
+    }
+
+    let otsu = img.otsu_threshold().0;
+    let mut sauvola_errors = 0;
+    let mut otsu_errors = 0;
+    let binarized = img.sauvola(15, 0.2, 128.0);
+    for y in 0..32 {
+        for x in 0..64 {
+            let expected = !is_text(x, y);
+            if binarized.get_pixel(x, y).unwrap().value() != expected {
+                sauvola_errors += 1;
+            }
+            if (img.get_pixel(x, y).unwrap().intensity().0 > otsu) != expected {
+                otsu_errors += 1;
+            }
+        }
+    }
+    assert!(sauvola_errors * 10 < otsu_errors,
+            "sauvola: {:}, otsu: {:}",
+            sauvola_errors,
+            otsu_errors);
+}
+
+#[test]
+fn test_bytes_round_trip() {
+    use {Pixel, ScalarVal, Gray16U, GrayVal16U};
+
+    let mut img = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(3, 2, 8);
+    for y in 0..2 {
+        for x in 0..3 {
+            img.set_pixel(x, y, GrayVal16U::new(ScalarVal((1000 * y + x) as u16)));
+        }
+    }
+
+    let bytes: Vec<u8> = img.bytes().collect();
+    assert_eq!(bytes.len(), 2 * Gray16U::calc_minimum_pitch(3, 1));
+
+    let round_trip = ImageBufferVal::<Gray16U>::from_bytes_iter(3, 2, 6, bytes.clone()).unwrap();
+    assert_eq!(round_trip.pitch(), 6);
+    for y in 0..2 {
+        for x in 0..3 {
+            assert_eq!(round_trip.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+
+    assert!(ImageBufferVal::<Gray16U>::from_bytes_iter(3, 2, 6, bytes[1..].to_vec()).is_none());
+    let mut too_long = bytes.clone();
+    too_long.push(0);
+    assert!(ImageBufferVal::<Gray16U>::from_bytes_iter(3, 2, 6, too_long).is_none());
+    // the pitch is too small for the width
+    assert!(ImageBufferVal::<Gray16U>::from_bytes_iter(3, 2, 5, bytes.clone()).is_none());
+}
+
+#[test]
+fn test_powf_and_sqrt() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    let values = [0.0f32, 1.0, 4.0, 2.25, -1.0];
+    let mut img = ImageBufferVal::<Gray32F>::new_with_size(values.len() as u32, 1);
+    for (x, value) in values.iter().enumerate() {
+        img.set_pixel(x as u32, 0, GrayVal32F::new(ScalarVal(*value)));
+    }
+
+    let roots = img.sqrt();
+    assert_eq!(roots.get_pixel(0, 0).unwrap().intensity(), ScalarVal(0.0));
+    assert_eq!(roots.get_pixel(1, 0).unwrap().intensity(), ScalarVal(1.0));
+    assert_eq!(roots.get_pixel(2, 0).unwrap().intensity(), ScalarVal(2.0));
+    assert_eq!(roots.get_pixel(3, 0).unwrap().intensity(), ScalarVal(1.5));
+    assert!(roots.get_pixel(4, 0).unwrap().intensity().0.is_nan());
+
+    let round_trip = img.powf(2.0).sqrt();
+    for x in 0..4 {
+        let expected = img.get_pixel(x, 0).unwrap().intensity().0;
+        assert!((round_trip.get_pixel(x, 0).unwrap().intensity().0 - expected).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_log1p_and_expm1() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    let values = [0.0f32, 0.5, 10.0, 1000.0, 65000.0];
+    let mut img = ImageBufferVal::<Gray32F>::new_with_size(values.len() as u32, 1);
+    for (x, value) in values.iter().enumerate() {
+        img.set_pixel(x as u32, 0, GrayVal32F::new(ScalarVal(*value)));
+    }
+
+    let compressed = img.log1p();
+    assert_eq!(compressed.get_pixel(0, 0).unwrap().intensity(), ScalarVal(0.0));
+    assert!(compressed.get_pixel(4, 0).unwrap().intensity().0 < 12.0);
+
+    let round_trip = compressed.expm1();
+    for (x, value) in values.iter().enumerate() {
+        let recovered = round_trip.get_pixel(x as u32, 0).unwrap().intensity().0;
+        assert!((recovered - value).abs() <= value.abs() * 1e-5);
+    }
+}
+
+#[test]
+fn test_hysteresis() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(8, 5);
+    // strong seed with a diagonal chain of weak pixels
+    img.set_pixel(0, 0, GrayVal8U::new(ScalarVal(200)));
+    for i in 1..5 {
+        img.set_pixel(i, i, GrayVal8U::new(ScalarVal(100)));
+    }
+    img.set_pixel(5, 4, GrayVal8U::new(ScalarVal(100)));
+    // isolated weak pixel
+    img.set_pixel(7, 0, GrayVal8U::new(ScalarVal(100)));
+    // below the low threshold
+    img.set_pixel(1, 0, GrayVal8U::new(ScalarVal(30)));
+
+    let edges = img.hysteresis(ScalarVal(50), ScalarVal(150));
+    for y in 0..5 {
+        for x in 0..8 {
+            let expected = x == y || (x == 5 && y == 4);
+            assert_eq!(edges.get_pixel(x, y).unwrap().value(), expected);
+        }
+    }
+}
+
+#[test]
+fn test_resize() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(4, 3);
+    for y in 0..3 {
+        for x in 0..4 {
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal((x * 60) as u8)));
+        }
+    }
+
+    for &filter in &[ResizeFilter::Nearest,
+                     ResizeFilter::Bilinear,
+                     ResizeFilter::Bicubic,
+                     ResizeFilter::Lanczos3] {
+        let enlarged = img.resize(8, 5, filter);
+        assert_eq!((enlarged.width(), enlarged.height()), (8, 5));
+        let shrunk = img.resize(2, 1, filter);
+        assert_eq!((shrunk.width(), shrunk.height()), (2, 1));
+        // the gradient is monotonic along x for every filter
+        assert!(enlarged.get_pixel(0, 2).unwrap().intensity().0 <
+                enlarged.get_pixel(7, 2).unwrap().intensity().0);
+        // identity size reproduces the input
+        let same = img.resize(4, 3, filter);
+        for y in 0..3 {
+            for x in 0..4 {
+                assert_eq!(same.get_pixel(x, y), img.get_pixel(x, y));
+            }
+        }
+    }
+
+    let nearest = img.resize_nearest(8, 3);
+    assert_eq!(nearest.get_pixel(0, 0).unwrap().intensity().0, 0);
+    assert_eq!(nearest.get_pixel(1, 0).unwrap().intensity().0, 0);
+    assert_eq!(nearest.get_pixel(2, 0).unwrap().intensity().0, 60);
+}
+
+#[test]
+fn test_accumulate_weighted() {
+    use {ScalarVal, Gray8U, GrayVal8U, Gray32F};
+
+    let mut frame = ImageBufferVal::<Gray8U>::new_with_size(3, 2);
+    for y in 0..2 {
+        for x in 0..3 {
+            frame.set_pixel(x, y, GrayVal8U::new(ScalarVal(100)));
+        }
+    }
+
+    let mut background = ImageBufferVal::<Gray32F>::new_with_size(3, 2);
+    let mut previous = f32::INFINITY;
+    for _ in 0..40 {
+        background.accumulate_weighted(&frame, 0.2);
+        let error = (100.0 - background.get_pixel(2, 1).unwrap().intensity().0).abs();
+        assert!(error < previous);
+        previous = error;
+    }
+    assert!(previous < 0.1);
+
+    let mut other = ImageBufferVal::<Gray8U>::new_with_size(3, 2);
+    other.set_pixel(1, 0, GrayVal8U::new(ScalarVal(7)));
+    background.accumulate_weighted(&other, 1.0);
+    assert_eq!(background.get_pixel(1, 0).unwrap().intensity().0, 7.0);
+    assert_eq!(background.get_pixel(0, 0).unwrap().intensity().0, 0.0);
+}
+
+#[test]
+fn test_foreground_mask() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    let mut background = ImageBufferVal::<Gray32F>::new_with_size(5, 4);
+    let mut frame = ImageBufferVal::<Gray32F>::new_with_size(5, 4);
+    for y in 0..4 {
+        for x in 0..5 {
+            background.set_pixel(x, y, GrayVal32F::new(ScalarVal(0.5)));
+            let inside = (1..3).contains(&x) && y >= 2;
+            let value = if inside { 0.1 } else { 0.55 };
+            frame.set_pixel(x, y, GrayVal32F::new(ScalarVal(value)));
+        }
+    }
+
+    let mask = background.foreground_mask(&frame, 0.2);
+    for y in 0..4 {
+        for x in 0..5 {
+            let inside = (1..3).contains(&x) && y >= 2;
+            assert_eq!(mask.get_pixel(x, y).unwrap().value(), inside);
+        }
+    }
+}
+
+#[test]
+fn test_to_png_data_url() {
+    use {ScalarVal, Gray8U, GrayVal8U, Rgb8U, RgbVal8U};
+
+    fn decode(url: &str) -> Vec<u8> {
+        let prefix = "data:image/png;base64,";
+        assert!(url.starts_with(prefix));
+
+        let mut png = Vec::new();
+        let mut bits = 0u32;
+        let mut bit_count = 0;
+        for c in url[prefix.len()..].bytes().take_while(|&c| c != b'=') {
+            let value = match c {
+                b'A'..=b'Z' => c - b'A',
+                b'a'..=b'z' => c - b'a' + 26,
+                b'0'..=b'9' => c - b'0' + 52,
+                b'+' => 62,
+                b'/' => 63,
+                _ => panic!("Invalid base64 character {:}", c as char),
+            };
+            bits = (bits << 6) | value as u32;
+            bit_count += 6;
+            if bit_count >= 8 {
+                bit_count -= 8;
+                png.push((bits >> bit_count) as u8);
+            }
+        }
+        png
+    }
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(3, 2);
+    for y in 0..2 {
+        for x in 0..3 {
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal((y * 3 + x) as u8 * 40)));
+        }
+    }
+    let png = decode(&img.to_png_data_url());
+
+    assert_eq!(png[..8], [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    assert_eq!(png[12..16], *b"IHDR");
+    assert_eq!(png[16..24], [0, 0, 0, 3, 0, 0, 0, 2]);
+    // the single stored deflate block holds the filtered rows
+    let idat = 8 + 25;
+    assert_eq!(png[idat + 4..idat + 8], *b"IDAT");
+    assert_eq!(png[idat + 15..idat + 23], [0, 0, 40, 80, 0, 120, 160, 200]);
+    assert_eq!(png[png.len() - 8..png.len() - 4], *b"IEND");
+
+    let mut img = ImageBufferVal::<Rgb8U>::new_with_size_and_pitch(2, 2, 8);
+    for y in 0..2 {
+        for x in 0..2 {
+            let base = (y * 60 + x * 30) as u8;
+            let pixel = RgbVal8U::new(ScalarVal(base), ScalarVal(base + 1), ScalarVal(255));
+            img.set_pixel(x, y, pixel);
+        }
+    }
+    let png = decode(&img.to_png_data_url());
+
+    assert_eq!(png[..8], [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    assert_eq!(png[12..16], *b"IHDR");
+    // width, height, bit depth and color type 2 (RGB)
+    assert_eq!(png[16..26], [0, 0, 0, 2, 0, 0, 0, 2, 8, 2]);
+    assert_eq!(png[idat + 4..idat + 8], *b"IDAT");
+    // two rows of a filter byte followed by the packed RGB triples without padding
+    assert_eq!(png[idat..idat + 4], [0, 0, 0, 2 + 5 + 14 + 4]);
+    assert_eq!(png[idat + 15..idat + 29],
+               [0, 0, 1, 255, 30, 31, 255, 0, 60, 61, 255, 90, 91, 255]);
+    assert_eq!(png[png.len() - 8..png.len() - 4], *b"IEND");
+}
+
+#[test]
+fn test_map_rows_and_cols() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(4, 3);
+    for y in 0..3 {
+        for x in 0..4 {
+            img.set_pixel(x, y, GrayVal8U::new(ScalarVal((y * 4 + x) as u8)));
+        }
+    }
+
+    let flipped = img.map_rows(|row| row.iter().rev().cloned().collect());
+    let mirrored = img.map_cols(|col| col.iter().rev().cloned().collect());
+    for y in 0..3 {
+        for x in 0..4 {
+            assert_eq!(flipped.get_pixel(x, y), img.get_pixel(3 - x, y));
+            assert_eq!(mirrored.get_pixel(x, y), img.get_pixel(x, 2 - y));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_map_rows_wrong_length() {
+    use Gray8U;
+
+    let img = ImageBufferVal::<Gray8U>::new_with_size(4, 3);
+    img.map_rows(|row| row[1..].to_vec());
+}
+
+#[test]
+fn test_positions_where() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut mask = ImageBufferVal::<Gray8U>::new_with_size(5, 4);
+    for &(x, y) in &[(4, 0), (1, 2), (0, 3), (3, 3), (2, 2)] {
+        mask.set_pixel(x, y, GrayVal8U::new(ScalarVal(255)));
+    }
+    let positions = mask.positions_where(|pixel| pixel.intensity().0 != 0);
+    assert_eq!(positions, vec![(4, 0), (1, 2), (2, 2), (0, 3), (3, 3)]);
+    assert!(mask.positions_where(|_| false).is_empty());
+}
+
+#[test]
+fn test_open_and_close() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    // 4x4 square with a pinhole, plus an isolated speck
+    let mut mask = ImageBufferVal::<Gray8U>::new_with_size(13, 8);
+    for y in 2..6 {
+        for x in 2..6 {
+            mask.set_pixel(x, y, GrayVal8U::new(ScalarVal(255)));
+        }
+    }
+    mask.set_pixel(3, 3, GrayVal8U::new(ScalarVal(0)));
+    mask.set_pixel(10, 5, GrayVal8U::new(ScalarVal(255)));
+    let is_set = |img: &ImageBufferVal<Gray8U>, x, y| {
+        img.get_pixel(x, y).unwrap().intensity().0 != 0
+    };
+
+    let closed = mask.close(1);
+    assert!(is_set(&closed, 2, 2));
+    for y in 0..8 {
+        for x in 0..13 {
+            if (x, y) != (3, 3) {
+                assert_eq!(is_set(&closed, x, y), is_set(&mask, x, y));
+            }
+        }
+    }
+
+    let mut solid = mask.clone();
+    solid.set_pixel(3, 3, GrayVal8U::new(ScalarVal(255)));
+    let opened = solid.open(1);
+    assert!(!is_set(&opened, 7, 5));
+    for y in 0..8 {
+        for x in 0..13 {
+            if (x, y) != (10, 5) {
+                assert_eq!(is_set(&opened, x, y), is_set(&solid, x, y));
+            }
+        }
+    }
+
+    // the structuring element covers the whole image without overflowing
+    let dilated = mask.dilate(u32::MAX);
+    let eroded = mask.erode(u32::MAX);
+    for y in 0..8 {
+        for x in 0..13 {
+            assert!(is_set(&dilated, x, y));
+            assert!(!is_set(&eroded, x, y));
+        }
+    }
+}
+
+#[test]
+fn test_skeletonize() {
+    use std::collections::VecDeque;
+    use {Binary, BinaryVal};
+
+    let mut mask = ImageBufferVal::<Binary>::new_with_size(16, 9);
+    for y in 2..7 {
+        for x in 2..14 {
+            mask.set_pixel(x, y, BinaryVal::new(true));
+        }
+    }
+    let skeleton = mask.skeletonize();
+    let is_set = |x: u32, y: u32| skeleton.get_pixel(x, y).unwrap().value();
+    let points = skeleton.positions_where(|pixel| pixel.value());
+    // a line along the medial axis, only shortened at its ends
+    assert!(points.len() >= 12 - 5);
+    for &(_, y) in &points {
+        assert_eq!(y, 4);
+    }
+
+    // thin: no 2x2 block is completely set
+    for y in 0..8 {
+        for x in 0..15 {
+            let block = [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)];
+            assert!(!block.iter().all(|&(bx, by)| is_set(bx, by)));
+        }
+    }
+
+    // connected: a flood fill from the first point reaches all of them
+    let mut visited = vec![points[0]];
+    let mut queue = VecDeque::new();
+    queue.push_back(points[0]);
+    while let Some((cx, cy)) = queue.pop_front() {
+        for &(x, y) in &points {
+            let adjacent = (x as i64 - cx as i64).abs() <= 1 && (y as i64 - cy as i64).abs() <= 1;
+            if adjacent && !visited.contains(&(x, y)) {
+                visited.push((x, y));
+                queue.push_back((x, y));
+            }
+        }
+    }
+    assert_eq!(visited.len(), points.len());
+}
+
+#[test]
+fn test_laplacian() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut gradient = ImageBufferVal::<Gray8U>::new_with_size(6, 5);
+    for y in 0..5 {
+        for x in 0..6 {
+            gradient.set_pixel(x, y, GrayVal8U::new(ScalarVal((10 * x + 20 * y) as u8)));
+        }
+    }
+    let response = gradient.laplacian();
+    for y in 1..4 {
+        for x in 1..5 {
+            assert!(response.get_pixel(x, y).unwrap().intensity().0.abs() < 1e-6);
+        }
+    }
+
+    let mut spot = ImageBufferVal::<Gray8U>::new_with_size(5, 5);
+    spot.set_pixel(2, 2, GrayVal8U::new(ScalarVal(10)));
+    let response = spot.laplacian();
+    for y in 0..5 {
+        for x in 0..5 {
+            let expected = match (x as i32 - 2).abs() + (y as i32 - 2).abs() {
+                0 => -40.0,
+                1 => 10.0,
+                _ => 0.0,
+            };
+            assert_eq!(response.get_pixel(x, y).unwrap().intensity().0, expected);
+        }
+    }
+}
+
+#[test]
+fn test_sharpness_variance_of_laplacian() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut sharp = ImageBufferVal::<Gray8U>::new_with_size(16, 16);
+    for y in 0..16 {
+        for x in 8..16 {
+            sharp.set_pixel(x, y, GrayVal8U::new(ScalarVal(200)));
+        }
+    }
+    let (mean, stddev) = sharp.mean_stddev();
+    assert_eq!(mean, 100.0);
+    assert_eq!(stddev, 100.0);
+
+    let blurred = sharp.gaussian_blur(1.5);
+    assert!(sharp.sharpness_variance_of_laplacian() > blurred.sharpness_variance_of_laplacian());
+    assert!(blurred.sharpness_variance_of_laplacian() > 0.0);
+}
+
+#[test]
+fn test_swap_rb() {
+    use {ScalarVal, Rgb8U, RgbVal8U};
+
+    let mut img = ImageBufferVal::<Rgb8U>::new_with_size(3, 2);
+    for y in 0..2 {
+        for x in 0..3 {
+            let v = (y * 3 + x) as u8;
+            let pixel = RgbVal8U::new(ScalarVal(v), ScalarVal(100 + v), ScalarVal(200 + v));
+            img.set_pixel(x, y, pixel);
+        }
+    }
+
+    let swapped = img.swap_rb();
+    let identity = img.reorder_channels(&[0, 1, 2]);
+    let round_trip = swapped.swap_rb();
+    for y in 0..2 {
+        for x in 0..3 {
+            let v = (y * 3 + x) as u8;
+            let expected = RgbVal8U::new(ScalarVal(200 + v), ScalarVal(100 + v), ScalarVal(v));
+            assert_eq!(swapped.get_pixel(x, y).unwrap(), expected);
+            assert_eq!(identity.get_pixel(x, y), img.get_pixel(x, y));
+            assert_eq!(round_trip.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_reorder_channels_wrong_length() {
+    use Rgb8U;
+
+    ImageBufferVal::<Rgb8U>::new_with_size(2, 2).reorder_channels(&[1, 0]);
+}
+
+#[test]
+#[should_panic]
+fn test_reorder_channels_out_of_range() {
+    use Rgb8U;
+
+    ImageBufferVal::<Rgb8U>::new_with_size(2, 2).reorder_channels(&[0, 1, 3]);
+}
+
+#[test]
+fn test_total_intensity() {
+    use {ScalarVal, Gray8U, GrayVal8U, Rgb8U, RgbVal8U};
+
+    let mut gray = ImageBufferVal::<Gray8U>::new_with_size(4, 3);
+    for y in 0..3 {
+        for x in 0..4 {
+            gray.set_pixel(x, y, GrayVal8U::new(ScalarVal(250)));
+        }
+    }
+    assert_eq!(gray.total_intensity(), 12.0 * 250.0);
+
+    let mut rgb = ImageBufferVal::<Rgb8U>::new_with_size(2, 1);
+    rgb.set_pixel(0, 0, RgbVal8U::new(ScalarVal(1), ScalarVal(2), ScalarVal(3)));
+    rgb.set_pixel(1, 0, RgbVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(10)));
+    assert_eq!(rgb.total_intensity(), 271.0);
+}
+
+#[test]
+fn test_flatten() {
+    use {ScalarVal, Rgba8U, RgbaVal8U, RgbVal8U};
+
+    let mut img = ImageBufferVal::<Rgba8U>::new_with_size(3, 1);
+    let rgba = |r, g, b, a| RgbaVal8U::new(ScalarVal(r), ScalarVal(g), ScalarVal(b), ScalarVal(a));
+    img.set_pixel(0, 0, rgba(255, 0, 0, 128));
+    img.set_pixel(1, 0, rgba(255, 0, 0, 0));
+    img.set_pixel(2, 0, rgba(10, 20, 30, 255));
+
+    let white = RgbVal8U::new(ScalarVal(255), ScalarVal(255), ScalarVal(255));
+    let flat = img.flatten(white);
+    assert_eq!(flat.get_pixel(0, 0).unwrap(),
+               RgbVal8U::new(ScalarVal(255), ScalarVal(127), ScalarVal(127)));
+    assert_eq!(flat.get_pixel(1, 0).unwrap(), white);
+    assert_eq!(flat.get_pixel(2, 0).unwrap(),
+               RgbVal8U::new(ScalarVal(10), ScalarVal(20), ScalarVal(30)));
+}
+
+#[test]
+fn test_local_maxima() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut img = ImageBufferVal::<Gray8U>::new_with_size(10, 6);
+    for &(x, y, v) in &[(2, 2, 200), (3, 2, 150), (7, 4, 180), (0, 0, 30), (9, 0, 90), (8, 0, 90)] {
+        img.set_pixel(x, y, GrayVal8U::new(ScalarVal(v)));
+    }
+
+    // (3, 2) is suppressed by its neighbor, the plateau at the top right by the strict rule
+    // and (0, 0) by the minimum value
+    assert_eq!(img.local_maxima(2, ScalarVal(50)), vec![(2, 2), (7, 4)]);
+    assert_eq!(img.local_maxima(1, ScalarVal(10)), vec![(0, 0), (2, 2), (7, 4)]);
+    // the neighborhood covers the whole image without overflowing
+    assert_eq!(img.local_maxima(u32::MAX, ScalarVal(50)), vec![(2, 2)]);
+}
+
+#[test]
+fn test_to_log_polar() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    // concentric rings: the intensity only depends on the distance to the center
+    let mut img = ImageBufferVal::<Gray32F>::new_with_size(41, 41);
+    for y in 0..41 {
+        for x in 0..41 {
+            let distance = ((x as f32 - 20.0).powi(2) + (y as f32 - 20.0).powi(2)).sqrt();
+            img.set_pixel(x, y, GrayVal32F::new(ScalarVal((distance * 0.5).sin())));
+        }
+    }
+
+    let polar = img.to_log_polar((20.0, 20.0), 32, 64);
+    assert_eq!((polar.width(), polar.height()), (32, 64));
+    let max_radius = 2f64.sqrt() * 20.0;
+    for x in 0..32 {
+        let radius = (x as f64 / 32.0 * max_radius.ln()).exp();
+        if radius > 19.0 {
+            break;
+        }
+        let expected = (radius * 0.5).sin();
+        for y in 0..64 {
+            let value = polar.get_pixel(x, y).unwrap().intensity().0 as f64;
+            assert!((value - expected).abs() < 0.05, "({:}, {:}): {:}", x, y, value);
+        }
+    }
+}
+
+#[test]
+fn test_vignette_and_radial_gradient() {
+    use {ScalarVal, Gray8U, GrayVal8U, Rgba8U, RgbaVal8U};
+
+    let gradient = ImageBufferVal::<Gray8U>::radial_gradient(9,
+                                                             7,
+                                                             GrayVal8U::new(ScalarVal(0)),
+                                                             GrayVal8U::new(ScalarVal(200)));
+    assert_eq!(gradient.get_pixel(4, 3).unwrap().intensity().0, 0);
+    assert_eq!(gradient.get_pixel(0, 0).unwrap().intensity().0, 200);
+    assert_eq!(gradient.get_pixel(8, 6).unwrap().intensity().0, 200);
+    assert_eq!(gradient.get_pixel(2, 3), gradient.get_pixel(6, 3));
+
+    let mut flat = ImageBufferVal::<Gray8U>::new_with_size(9, 7);
+    for y in 0..7 {
+        for x in 0..9 {
+            flat.set_pixel(x, y, GrayVal8U::new(ScalarVal(200)));
+        }
+    }
+    let unchanged = flat.apply_vignette(0.0);
+    let vignetted = flat.apply_vignette(0.5);
+    let value = |x, y| vignetted.get_pixel(x, y).unwrap().intensity().0;
+    assert_eq!(value(4, 3), 200);
+    assert_eq!(value(0, 0), 100);
+    for y in 0..7 {
+        for x in 0..9 {
+            assert_eq!(unchanged.get_pixel(x, y), flat.get_pixel(x, y));
+            // symmetric about the center
+            assert_eq!(value(x, y), value(8 - x, 6 - y));
+            assert!(value(x, y) >= value(0, 0));
+        }
+    }
+
+    // the alpha channel is not darkened
+    let mut rgba = ImageBufferVal::<Rgba8U>::new_with_size(9, 7);
+    let pixel = RgbaVal8U::new(ScalarVal(200), ScalarVal(100), ScalarVal(50), ScalarVal(128));
+    for y in 0..7 {
+        for x in 0..9 {
+            rgba.set_pixel(x, y, pixel);
+        }
+    }
+    let corner = rgba.apply_vignette(1.0).get_pixel(0, 0).unwrap();
+    assert_eq!(corner,
+               RgbaVal8U::new(ScalarVal(0), ScalarVal(0), ScalarVal(0), ScalarVal(128)));
+}
+
+#[test]
+fn test_draw_circle() {
+    use std::collections::VecDeque;
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let white = GrayVal8U::new(ScalarVal(255));
+    let mut filled = ImageBufferVal::<Gray8U>::new_with_size(16, 16);
+    filled.draw_circle(6, 7, 5, white, true);
+    // clipped at the top left
+    filled.draw_circle(-1, -1, 2, white, true);
+    for y in 0..16i64 {
+        for x in 0..16i64 {
+            let inside = (x - 6).pow(2) + (y - 7).pow(2) <= 25 ||
+                         (x + 1).pow(2) + (y + 1).pow(2) <= 4;
+            assert_eq!(filled.get_pixel(x as u32, y as u32).unwrap() == white, inside);
+        }
+    }
+
+    let mut outline = ImageBufferVal::<Gray8U>::new_with_size(16, 16);
+    outline.draw_circle(8, 8, 6, white, false);
+    let points = outline.positions_where(|pixel| pixel == white);
+    for &p in &[(14, 8), (2, 8), (8, 2), (8, 14)] {
+        assert!(points.contains(&p));
+    }
+    assert!(!points.contains(&(8, 8)));
+    for &(x, y) in &points {
+        let distance = ((x as f64 - 8.0).powi(2) + (y as f64 - 8.0).powi(2)).sqrt();
+        assert!((distance - 6.0).abs() < 0.75);
+    }
+    // connected ring: a flood fill along set pixels reaches all of them
+    let mut visited = vec![points[0]];
+    let mut queue = VecDeque::new();
+    queue.push_back(points[0]);
+    while let Some((cx, cy)) = queue.pop_front() {
+        for &(x, y) in &points {
+            let adjacent = (x as i64 - cx as i64).abs() <= 1 && (y as i64 - cy as i64).abs() <= 1;
+            if adjacent && !visited.contains(&(x, y)) {
+                visited.push((x, y));
+                queue.push_back((x, y));
+            }
+        }
+    }
+    assert_eq!(visited.len(), points.len());
+}
+
+#[test]
+fn test_estimate_shift() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    let pattern = |x: i64, y: i64| {
+        let (x, y) = (x.rem_euclid(32) as f32, y.rem_euclid(16) as f32);
+        (x * 0.7).sin() * (y * 1.3).cos() + if x > 10.0 && y < 5.0 { 1.0 } else { 0.0 }
+    };
+    let mut img = ImageBufferVal::<Gray32F>::new_with_size(32, 16);
+    let mut shifted = ImageBufferVal::<Gray32F>::new_with_size(32, 16);
+    for y in 0..16 {
+        for x in 0..32 {
+            img.set_pixel(x, y, GrayVal32F::new(ScalarVal(pattern(x as i64, y as i64))));
+            let value = pattern(x as i64 - 5, y as i64 + 3);
+            shifted.set_pixel(x, y, GrayVal32F::new(ScalarVal(value)));
+        }
+    }
+
+    assert_eq!(img.estimate_shift(&img), (0.0, 0.0));
+    let (dx, dy) = img.estimate_shift(&shifted);
+    assert!((dx - 5.0).abs() < 0.1 && (dy + 3.0).abs() < 0.1, "({:}, {:})", dx, dy);
+}
+
+#[test]
+fn test_fft2_round_trip() {
+    use {ScalarVal, Complex32F, ComplexVal32F};
+
+    for &(width, height) in &[(8, 8), (6, 5)] {
+        let mut img = ImageBufferVal::<Complex32F>::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let re = (x * 3 + y * 7) as f32 % 5.0 - 2.0;
+                let im = (x as f32 * 0.3).sin() + y as f32;
+                img.set_pixel(x, y, ComplexVal32F::new(ScalarVal(re), ScalarVal(im)));
+            }
+        }
+
+        let spectrum = img.fft2();
+        // the zero frequency holds the sum of all values
+        let (mut sum_re, mut sum_im) = (0.0, 0.0);
+        for y in 0..height {
+            for x in 0..width {
+                sum_re += img.get_pixel(x, y).unwrap().re().0;
+                sum_im += img.get_pixel(x, y).unwrap().im().0;
+            }
+        }
+        let dc = spectrum.get_pixel(0, 0).unwrap();
+        assert!((dc.re().0 - sum_re).abs() < 1e-3 && (dc.im().0 - sum_im).abs() < 1e-3);
+
+        let restored = spectrum.ifft2();
+        for y in 0..height {
+            for x in 0..width {
+                let (a, b) = (img.get_pixel(x, y).unwrap(), restored.get_pixel(x, y).unwrap());
+                assert!((a.re().0 - b.re().0).abs() < 1e-4);
+                assert!((a.im().0 - b.im().0).abs() < 1e-4);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lowpass_and_highpass() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    // checkerboard plus a slow horizontal wave
+    let mut img = ImageBufferVal::<Gray32F>::new_with_size(16, 8);
+    for y in 0..8 {
+        for x in 0..16 {
+            let wave = (2.0 * ::std::f32::consts::PI * x as f32 / 16.0).cos();
+            let checker = if (x + y) % 2 == 0 { 1.0 } else { 0.0 };
+            img.set_pixel(x, y, GrayVal32F::new(ScalarVal(0.5 + wave + checker)));
+        }
+    }
+
+    let low = img.lowpass(0.2);
+    let high = img.highpass(0.2);
+    for y in 0..8 {
+        for x in 0..16 {
+            let wave = (2.0 * ::std::f32::consts::PI * x as f32 / 16.0).cos();
+            let checker = if (x + y) % 2 == 0 { 0.5 } else { -0.5 };
+            let low_value = low.get_pixel(x, y).unwrap().intensity().0;
+            let high_value = high.get_pixel(x, y).unwrap().intensity().0;
+            assert!((low_value - (1.0 + wave)).abs() < 1e-4);
+            assert!((high_value - checker).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn test_hough_lines() {
+    use std::f64::consts::PI;
+    use {Binary, BinaryVal};
+
+    let mut edges = ImageBufferVal::<Binary>::new_with_size(24, 20);
+    for x in 2..22 {
+        edges.set_pixel(x, 7, BinaryVal::new(true));
+    }
+    // a few noise pixels
+    edges.set_pixel(3, 15, BinaryVal::new(true));
+    edges.set_pixel(17, 2, BinaryVal::new(true));
+
+    // the accumulator has a plateau within the quantization, the first cell is reported
+    let lines = edges.hough_lines(1.0, PI / 180.0, 10);
+    assert!(!lines.is_empty());
+    let (rho, theta) = lines[0];
+    assert!((rho - 7.0).abs() < 1e-9);
+    assert!((theta - PI / 2.0).abs() < 1.5 * PI / 180.0);
+    for &(_, theta) in &lines {
+        assert!((theta - PI / 2.0).abs() < 0.2);
+    }
+
+    // a vertical line at x = 5
+    let mut edges = ImageBufferVal::<Binary>::new_with_size(24, 20);
+    for y in 0..20 {
+        edges.set_pixel(5, y, BinaryVal::new(true));
+    }
+    assert_eq!(edges.hough_lines(1.0, PI / 90.0, 15)[0], (5.0, 0.0));
+}
+
+#[test]
+fn test_resize_premultiplied() {
+    use {ScalarVal, Rgba8U, RgbaVal8U};
+
+    // transparent black on the left, opaque red on the right
+    let red = RgbaVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(0), ScalarVal(255));
+    let mut img = ImageBufferVal::<Rgba8U>::new_with_size(8, 4);
+    for y in 0..4 {
+        for x in 4..8 {
+            img.set_pixel(x, y, red);
+        }
+    }
+
+    let plain = img.resize(3, 2, ResizeFilter::Bilinear);
+    let premultiplied = img.resize_premultiplied(3, 2, ResizeFilter::Bilinear);
+    let boundary = premultiplied.get_pixel(1, 0).unwrap();
+    assert!(boundary.a().0 > 0 && boundary.a().0 < 255);
+    // no dark fringe: the color stays pure red, whereas the plain resize darkens it
+    assert_eq!((boundary.r().0, boundary.g().0, boundary.b().0), (255, 0, 0));
+    assert!(plain.get_pixel(1, 0).unwrap().r().0 < 200);
+    // transparent regions stay color neutral, opaque ones equal the plain resize
+    assert_eq!(premultiplied.get_pixel(0, 0).unwrap(),
+               RgbaVal8U::new(ScalarVal(0), ScalarVal(0), ScalarVal(0), ScalarVal(0)));
+    assert_eq!(premultiplied.get_pixel(2, 1), plain.get_pixel(2, 1));
+}
+
+#[test]
+fn test_split_rows_mut() {
+    use std::thread;
+    use Gray16U;
+
+    let mut img = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(3, 7, 8);
+    {
+        let bands = img.split_rows_mut(3);
+        let layout: Vec<(u32, usize)> =
+            bands.iter().map(|&(row, ref data)| (row, data.len())).collect();
+        assert_eq!(layout, vec![(0, 24), (3, 16), (5, 16)]);
+        thread::scope(|scope| for (row, data) in bands {
+            scope.spawn(move || for value in data.iter_mut() {
+                *value = row as u8 + 1;
+            });
+        });
+    }
+    for y in 0..7 {
+        let expected = match y {
+            0..=2 => 0x0101,
+            3 | 4 => 0x0404,
+            _ => 0x0606,
+        };
+        for x in 0..3 {
+            assert_eq!(img.get_pixel(x, y).unwrap().intensity().0, expected);
+        }
+    }
+    assert_eq!(img.split_rows_mut(10).len(), 7);
+}
+
+#[test]
+fn test_pyramid_blend() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut bright = ImageBufferVal::<Gray8U>::new_with_size(64, 16);
+    let dark = ImageBufferVal::<Gray8U>::new_with_size(64, 16);
+    let mut half = ImageBufferVal::<Gray8U>::new_with_size(64, 16);
+    let mut ones = ImageBufferVal::<Gray8U>::new_with_size(64, 16);
+    let zeros = ImageBufferVal::<Gray8U>::new_with_size(64, 16);
+    for y in 0..16 {
+        for x in 0..64 {
+            bright.set_pixel(x, y, GrayVal8U::new(ScalarVal(200 + (x % 3) as u8)));
+            ones.set_pixel(x, y, GrayVal8U::new(ScalarVal(255)));
+            if x < 32 {
+                half.set_pixel(x, y, GrayVal8U::new(ScalarVal(255)));
+            }
+        }
+    }
+
+    let all_self = bright.pyramid_blend(&dark, &ones, 4);
+    let all_other = bright.pyramid_blend(&dark, &zeros, 4);
+    for y in 0..16 {
+        for x in 0..64 {
+            assert_eq!(all_self.get_pixel(x, y), bright.get_pixel(x, y));
+            assert_eq!(all_other.get_pixel(x, y), dark.get_pixel(x, y));
+        }
+    }
+
+    let hard = bright.pyramid_blend(&dark, &half, 0);
+    let smooth = bright.pyramid_blend(&dark, &half, 3);
+    let value = |img: &ImageBufferVal<Gray8U>, x| img.get_pixel(x, 8).unwrap().intensity().0 as i32;
+    assert_eq!((value(&hard, 31), value(&hard, 32)), (201, 0));
+    assert!(value(&smooth, 2) > 190 && value(&smooth, 61) < 10);
+    // the transition is spread over several pixels without large jumps
+    for x in 1..64 {
+        assert!(value(&smooth, x - 1) - value(&smooth, x) < 20);
+    }
+    assert!((28..36).all(|x| value(&smooth, x) > value(&smooth, x + 1)));
+    assert!(value(&smooth, 28) < 190 && value(&smooth, 36) > 10);
+}
+
+#[test]
+fn test_nl_means() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    let mut image = ImageBufferVal::<Gray32F>::new_with_size(16, 16);
+    let mut state = 12345u32;
+    for y in 0..16 {
+        for x in 0..16 {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            let noise = ((state >> 16) % 1000) as f32 / 1000.0 * 0.1 - 0.05;
+            let base = if x < 8 { 0.2 } else { 0.8 };
+            image.set_pixel(x, y, GrayVal32F::new(ScalarVal(base + noise)));
+        }
+    }
+
+    let variance = |img: &ImageBufferVal<Gray32F>| {
+        let values: Vec<f64> = (2..14)
+            .flat_map(|y| (1..6).map(move |x| (x, y)))
+            .map(|(x, y)| img.get_pixel(x, y).unwrap().intensity().0 as f64)
+            .collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / values.len() as f64
+    };
+    let denoised = image.nl_means(0.1, 1, 3);
+    assert!(variance(&denoised) < 0.5 * variance(&image));
+    for y in 0..16 {
+        assert!(denoised.get_pixel(7, y).unwrap().intensity().0 < 0.3);
+        assert!(denoised.get_pixel(8, y).unwrap().intensity().0 > 0.7);
+    }
+}
+
+#[test]
+fn test_remove_vertical_seam() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    // textured image with a uniform stripe in columns 5 to 7
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(10, 6);
+    for y in 0..6 {
+        for x in 0..10 {
+            let value = if (5..8).contains(&x) { 100 } else { ((x * 7 + y * 13) % 5) * 50 };
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(value as u8)));
+        }
+    }
+
+    let carved = image.remove_vertical_seam();
+    assert_eq!((carved.width(), carved.height()), (9, 6));
+    for y in 0..6 {
+        for x in 0..9 {
+            let sx = if x < 6 { x } else { x + 1 };
+            assert_eq!(carved.get_pixel(x, y), image.get_pixel(sx, y));
+        }
+    }
+
+    let narrow = image.seam_carve_to_width(4);
+    assert_eq!((narrow.width(), narrow.height()), (4, 6));
+    assert_eq!(image.seam_carve_to_width(10).get_pixel(3, 2), image.get_pixel(3, 2));
+}
+
+#[test]
+fn test_energy_map() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(8, 4);
+    for y in 0..4 {
+        for x in 4..8 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(100)));
+        }
+    }
+
+    let energy = image.energy_map();
+    assert_eq!((energy.width(), energy.height()), (8, 4));
+    for y in 0..4 {
+        for x in 0..8 {
+            let value = energy.get_pixel(x, y).unwrap().intensity().0;
+            if x == 3 || x == 4 {
+                assert_eq!(value, 100.0);
+            } else {
+                assert_eq!(value, 0.0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_match_histogram() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    // low contrast source with 16 levels, reference with all 256 levels once
+    let mut source = ImageBufferVal::<Gray8U>::new_with_size(16, 16);
+    let mut reference = ImageBufferVal::<Gray8U>::new_with_size(16, 16);
+    for y in 0..16 {
+        for x in 0..16 {
+            source.set_pixel(x, y, GrayVal8U::new(ScalarVal(100 + x as u8)));
+            reference.set_pixel(x, y, GrayVal8U::new(ScalarVal((y * 16 + x) as u8)));
+        }
+    }
+
+    let matched = source.match_histogram(&reference);
+    let bins = matched.histogram();
+    for (value, &count) in bins.iter().enumerate() {
+        assert_eq!(count, if value % 16 == 15 { 16 } else { 0 });
+    }
+    assert_eq!(matched.get_pixel(0, 3).unwrap().intensity(), ScalarVal(15));
+    assert_eq!(matched.get_pixel(15, 3).unwrap().intensity(), ScalarVal(255));
+
+    let identity = reference.match_histogram(&reference);
+    for y in 0..16 {
+        for x in 0..16 {
+            assert_eq!(identity.get_pixel(x, y), reference.get_pixel(x, y));
+        }
+    }
+}
+
+#[test]
+fn test_add_noise() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(16, 16);
+    for y in 0..16 {
+        for x in 0..16 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(128)));
+        }
+    }
+    let pixels = |img: &ImageBufferVal<Gray8U>| -> Vec<u8> {
+        (0..16)
+            .flat_map(|y| (0..16).map(move |x| (x, y)))
+            .map(|(x, y)| img.get_pixel(x, y).unwrap().intensity().0)
+            .collect()
+    };
+
+    let noisy = pixels(&image.add_gaussian_noise(10.0, 1));
+    assert_eq!(noisy, pixels(&image.add_gaussian_noise(10.0, 1)));
+    assert_ne!(noisy, pixels(&image.add_gaussian_noise(10.0, 2)));
+    let mean = noisy.iter().map(|&v| v as f64).sum::<f64>() / 256.0;
+    assert!((mean - 128.0).abs() < 3.0);
+    assert!(noisy.iter().any(|&v| v != 128));
+
+    let salted = pixels(&image.add_salt_pepper(0.2, 7));
+    assert_eq!(salted, pixels(&image.add_salt_pepper(0.2, 7)));
+    assert_ne!(salted, pixels(&image.add_salt_pepper(0.2, 8)));
+    assert!(salted.iter().all(|&v| v == 0 || v == 128 || v == 255));
+    assert!(salted.contains(&0) && salted.contains(&255));
+    assert_eq!(pixels(&image.add_salt_pepper(0.0, 7)), pixels(&image));
+}
+
+#[test]
+fn test_crop_and_pad_to_aspect() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(16, 9);
+    for y in 0..9 {
+        for x in 0..16 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal((y * 16 + x + 1) as u8)));
+        }
+    }
+
+    let cropped = image.crop_to_aspect(1, 1);
+    assert_eq!((cropped.width(), cropped.height()), (9, 9));
+    for y in 0..9 {
+        for x in 0..9 {
+            assert_eq!(cropped.get_pixel(x, y), image.get_pixel(x + 3, y));
+        }
+    }
+
+    let fill = GrayVal8U::new(ScalarVal(0));
+    let padded = image.pad_to_aspect(1, 1, fill);
+    assert_eq!((padded.width(), padded.height()), (16, 16));
+    for y in 0..16 {
+        for x in 0..16 {
+            let expected = if (3..12).contains(&y) {
+                image.get_pixel(x, y - 3)
+            } else {
+                Some(fill)
+            };
+            assert_eq!(padded.get_pixel(x, y), expected);
+        }
+    }
+
+    let tall = cropped.pad_to_aspect(3, 1, fill);
+    assert_eq!((tall.width(), tall.height()), (27, 9));
+    assert_eq!(tall.get_pixel(9, 0), cropped.get_pixel(0, 0));
+
+    for result in &[image.crop_to_aspect(16, 9), image.pad_to_aspect(32, 18, fill)] {
+        assert_eq!((result.width(), result.height()), (16, 9));
+        for y in 0..9 {
+            for x in 0..16 {
+                assert_eq!(result.get_pixel(x, y), image.get_pixel(x, y));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_moments() {
+    use std::f64::consts::PI;
+    use {ScalarVal, Binary, BinaryVal, Gray8U, GrayVal8U};
+
+    // 4x4 square centered in a 10x8 image
+    let mut mask = ImageBufferVal::<Binary>::new_with_size(10, 8);
+    let mut gray = ImageBufferVal::<Gray8U>::new_with_size(10, 8);
+    for y in 2..6 {
+        for x in 3..7 {
+            mask.set_pixel(x, y, BinaryVal::new(true));
+            gray.set_pixel(x, y, GrayVal8U::new(ScalarVal(255)));
+        }
+    }
+
+    let moments = mask.moments();
+    assert_eq!(moments.m00, 16.0);
+    assert_eq!(moments.centroid(), (4.5, 3.5));
+    assert_eq!(moments.mu11, 0.0);
+    assert_eq!(moments.mu20, moments.mu02);
+    assert_eq!(moments.orientation(), 0.0);
+
+    let intensity = gray.intensity_moments();
+    assert_eq!(intensity.m00, 16.0 * 255.0);
+    assert_eq!(intensity.centroid(), (4.5, 3.5));
+
+    let mut diagonal = ImageBufferVal::<Binary>::new_with_size(8, 8);
+    for i in 0..8 {
+        diagonal.set_pixel(i, i, BinaryVal::new(true));
+    }
+    assert!((diagonal.moments().orientation() - PI / 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_remap() {
+    use {ScalarVal, Gray8U, GrayVal8U, Gray32F, GrayVal32F};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(6, 4);
+    let mut map_x = ImageBufferVal::<Gray32F>::new_with_size(6, 4);
+    let mut map_y = ImageBufferVal::<Gray32F>::new_with_size(6, 4);
+    for y in 0..4 {
+        for x in 0..6 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal((y * 6 + x + 1) as u8 * 10)));
+            map_x.set_pixel(x, y, GrayVal32F::new(ScalarVal(x as f32)));
+            map_y.set_pixel(x, y, GrayVal32F::new(ScalarVal(y as f32)));
+        }
+    }
+
+    let identity = image.remap(&map_x, &map_y, BorderMode::Zero);
+    for y in 0..4 {
+        for x in 0..6 {
+            assert_eq!(identity.get_pixel(x, y), image.get_pixel(x, y));
+        }
+    }
+
+    // shift the content by 1.5 pixels to the right
+    for y in 0..4 {
+        for x in 0..6 {
+            map_x.set_pixel(x, y, GrayVal32F::new(ScalarVal(x as f32 - 1.5)));
+        }
+    }
+    let shifted = image.remap(&map_x, &map_y, BorderMode::Zero);
+    let clamped = image.remap(&map_x, &map_y, BorderMode::Clamp);
+    let value = |img: &ImageBufferVal<Gray8U>, x, y| img.get_pixel(x, y).unwrap().intensity().0;
+    for y in 0..4 {
+        for x in 2..6 {
+            let expected = (value(&image, x - 2, y) as u32 + value(&image, x - 1, y) as u32) / 2;
+            assert_eq!(value(&shifted, x, y) as u32, expected);
+        }
+        assert_eq!(value(&shifted, 0, y), 0);
+        assert_eq!(value(&shifted, 1, y), value(&image, 0, y) / 2);
+        assert_eq!(value(&clamped, 0, y), value(&image, 0, y));
+    }
+}
+
+#[test]
+fn test_stack_mean_and_median() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(8, 8);
+    for y in 0..8 {
+        for x in 0..8 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(100)));
+        }
+    }
+    let max_error = |img: &ImageBufferVal<Gray8U>| {
+        (0..8)
+            .flat_map(|y| (0..8).map(move |x| (x, y)))
+            .map(|(x, y)| (img.get_pixel(x, y).unwrap().intensity().0 as i32 - 100).abs())
+            .max()
+            .unwrap()
+    };
+
+    let frames: Vec<_> = (0..64).map(|seed| image.add_gaussian_noise(10.0, seed)).collect();
+    let mean = stack_mean(&frames);
+    assert!(max_error(&frames[0]) > 15);
+    assert!(max_error(&mean) <= 5);
+    assert!((mean.mean_intensity() - 100.0).abs() < 1.0);
+    assert!(max_error(&stack_mean(&frames[..4])) > max_error(&mean));
+
+    // outliers in a few of the noisy frames do not affect the median
+    let mut frames = frames;
+    for frame in frames.iter_mut().take(8) {
+        frame.set_pixel(2, 3, GrayVal8U::new(ScalarVal(255)));
+        frame.set_pixel(5, 5, GrayVal8U::new(ScalarVal(0)));
+    }
+    let median = stack_median(&frames);
+    assert!(max_error(&median) <= 6);
+    assert!((median.mean_intensity() - 100.0).abs() < 1.0);
+    assert!(max_error(&stack_mean(&frames)) > 15);
+
+    let mut frames: Vec<_> = (0..3).map(|_| image.clone()).collect();
+    frames[1].set_pixel(2, 3, GrayVal8U::new(ScalarVal(255)));
+    frames[2].set_pixel(5, 5, GrayVal8U::new(ScalarVal(0)));
+    assert_eq!(max_error(&stack_median(&frames)), 0);
+    let pair = stack_median(&frames[..2]);
+    assert_eq!(pair.get_pixel(2, 3).unwrap().intensity(), ScalarVal(178));
+}
+
+#[test]
+#[should_panic]
+fn test_stack_mean_size_mismatch() {
+    use Gray8U;
+
+    let images = vec![ImageBufferVal::<Gray8U>::new_with_size(8, 8),
+                      ImageBufferVal::<Gray8U>::new_with_size(8, 7)];
+    stack_mean(&images);
+}
+
+#[test]
+fn test_entropy() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(32, 16);
+    for y in 0..16 {
+        for x in 0..32 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(77)));
+        }
+    }
+    assert_eq!(image.entropy(), 0.0);
+
+    for y in 0..16 {
+        for x in 0..32 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(((y * 32 + x) % 256) as u8)));
+        }
+    }
+    assert!((image.entropy() - 8.0).abs() < 1e-12);
+
+    for y in 0..16 {
+        for x in 0..32 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(if x < 16 { 0 } else { 255 })));
+        }
+    }
+    assert!((image.entropy() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_demosaic() {
+    use {ScalarVal, Gray16U, GrayVal16U, RgbVal16U};
+
+    // uniform color (1000, 2000, 3000) and a horizontal red ramp sampled with RGGB
+    let mut flat = ImageBufferVal::<Gray16U>::new_with_size(8, 6);
+    let mut ramp = ImageBufferVal::<Gray16U>::new_with_size(8, 6);
+    for y in 0..6 {
+        for x in 0..8 {
+            let (flat_value, ramp_value) = match (x % 2, y % 2) {
+                (0, 0) => (1000, 100 * x as u16),
+                (1, 1) => (3000, 3000),
+                _ => (2000, 2000),
+            };
+            flat.set_pixel(x, y, GrayVal16U::new(ScalarVal(flat_value)));
+            ramp.set_pixel(x, y, GrayVal16U::new(ScalarVal(ramp_value)));
+        }
+    }
+
+    let color = RgbVal16U::new(ScalarVal(1000), ScalarVal(2000), ScalarVal(3000));
+    let rgb = flat.demosaic(BayerPattern::Rggb);
+    for y in 0..6 {
+        for x in 0..8 {
+            assert_eq!(rgb.get_pixel(x, y), Some(color));
+        }
+    }
+
+    let rgb = ramp.demosaic(BayerPattern::Rggb);
+    for y in 1..5 {
+        for x in 1..6 {
+            assert_eq!(rgb.get_pixel(x, y).unwrap().r(), ScalarVal(100 * x as u16));
+        }
+    }
+
+    // the same data read with a shifted phase swaps red and blue
+    let rgb = flat.demosaic(BayerPattern::Bggr);
+    let swapped = RgbVal16U::new(ScalarVal(3000), ScalarVal(2000), ScalarVal(1000));
+    assert_eq!(rgb.get_pixel(3, 3), Some(swapped));
+}
+
+#[test]
+fn test_white_balance_grayworld() {
+    use {ScalarVal, Rgb8U, RgbVal8U};
+
+    let mut image = ImageBufferVal::<Rgb8U>::new_with_size(8, 8);
+    for y in 0..8 {
+        for x in 0..8 {
+            let v = (x * 8 + y * 4) as u8;
+            let pixel = RgbVal8U::new(ScalarVal(40 + v), ScalarVal(50 + v), ScalarVal(150 + v));
+            image.set_pixel(x, y, pixel);
+        }
+    }
+    let means = |img: &ImageBufferVal<Rgb8U>| {
+        let mut means = [0.0; 3];
+        for y in 0..8 {
+            for x in 0..8 {
+                let pixel = img.get_pixel(x, y).unwrap();
+                means[0] += pixel.r().0 as f64 / 64.0;
+                means[1] += pixel.g().0 as f64 / 64.0;
+                means[2] += pixel.b().0 as f64 / 64.0;
+            }
+        }
+        means
+    };
+
+    let balanced = image.white_balance_grayworld();
+    let [r, g, b] = means(&balanced);
+    assert!((r - g).abs() < 1.0 && (g - b).abs() < 1.0);
+    assert!((r - 122.0).abs() < 1.0);
+
+    let again = balanced.white_balance_grayworld();
+    for y in 0..8 {
+        for x in 0..8 {
+            let (a, b) = (again.get_pixel(x, y).unwrap(), balanced.get_pixel(x, y).unwrap());
+            assert!((a.r().0 as i32 - b.r().0 as i32).abs() <= 1);
+            assert!((a.b().0 as i32 - b.b().0 as i32).abs() <= 1);
+        }
+    }
+}
+
+#[test]
+fn test_draw_text() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(14, 9);
+    image.draw_text(1, 1, "Hi", GrayVal8U::new(ScalarVal(255)));
+    let set = |x, y| image.get_pixel(x, y).unwrap().intensity().0 == 255;
+    // 'H': both vertical bars and the crossbar in the fourth row
+    assert!((1..8).all(|y| set(1, y) && set(5, y)));
+    assert!((1..6).all(|x| set(x, 4)));
+    assert!(!set(3, 1));
+    // 'I' is drawn uppercase, its stem is in the middle of the second glyph
+    assert!((1..8).all(|y| set(9, y)));
+    assert!(!set(7, 4) && !set(11, 4));
+    assert!((0..14).all(|x| !set(x, 0) && !set(x, 8)));
+}
+
+#[test]
+fn test_make_contact_sheet() {
+    use {ScalarVal, Rgb8U, RgbVal8U};
+
+    let solid = |width, height, r, g, b| {
+        let mut image = ImageBufferVal::<Rgb8U>::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                image.set_pixel(x, y, RgbVal8U::new(ScalarVal(r), ScalarVal(g), ScalarVal(b)));
+            }
+        }
+        image
+    };
+    let images = vec![("red".to_string(), solid(40, 20, 255, 0, 0)),
+                      ("green".to_string(), solid(10, 10, 0, 255, 0)),
+                      ("blue".to_string(), solid(15, 30, 0, 0, 255))];
+
+    let sheet = ImageBufferVal::make_contact_sheet(&images, 20, 20, 2);
+    // cells of 28x39 pixels in two columns and two rows
+    assert_eq!((sheet.width(), sheet.height()), (56, 78));
+    let background = RgbVal8U::new(ScalarVal(48), ScalarVal(48), ScalarVal(48));
+    assert_eq!(sheet.get_pixel(14, 14).unwrap().r(), ScalarVal(255));
+    assert_eq!(sheet.get_pixel(42, 14).unwrap().g(), ScalarVal(255));
+    assert_eq!(sheet.get_pixel(14, 53).unwrap().b(), ScalarVal(255));
+    // the red thumbnail is 20x10 and centered vertically
+    assert_eq!(sheet.get_pixel(14, 6), Some(background));
+    assert_eq!(sheet.get_pixel(14, 9).unwrap().r(), ScalarVal(255));
+    // the blue thumbnail is 10x20 and centered horizontally
+    assert_eq!(sheet.get_pixel(6, 53), Some(background));
+    // labels below the thumbnails, the fourth cell stays empty
+    let white = RgbVal8U::new(ScalarVal(255), ScalarVal(255), ScalarVal(255));
+    let has_text = |x0: u32, y0: u32| {
+        (y0..y0 + 7).any(|y| (x0..x0 + 24).any(|x| sheet.get_pixel(x, y) == Some(white)))
+    };
+    assert!(has_text(4, 28) && has_text(32, 28) && has_text(4, 67));
+    assert!((39..78).all(|y| (28..56).all(|x| sheet.get_pixel(x, y) == Some(background))));
+}
+
+#[test]
+fn test_diff_image() {
+    use {ScalarVal, Gray8U, GrayVal8U, RgbVal8U};
+
+    let mut a = ImageBufferVal::<Gray8U>::new_with_size(4, 3);
+    for y in 0..3 {
+        for x in 0..4 {
+            a.set_pixel(x, y, GrayVal8U::new(ScalarVal(100)));
+        }
+    }
+    let gray = RgbVal8U::new(ScalarVal(128), ScalarVal(128), ScalarVal(128));
+    let same = a.diff_image(&a, 10.0);
+    for y in 0..3 {
+        for x in 0..4 {
+            assert_eq!(same.get_pixel(x, y), Some(gray));
+        }
+    }
+
+    let mut b = ImageBufferVal::<Gray8U>::new_with_size(4, 3);
+    for y in 0..3 {
+        for x in 0..4 {
+            b.set_pixel(x, y, GrayVal8U::new(ScalarVal(100)));
+        }
+    }
+    a.set_pixel(1, 1, GrayVal8U::new(ScalarVal(200)));
+    a.set_pixel(2, 1, GrayVal8U::new(ScalarVal(90)));
+    let diff = a.diff_image(&b, 4.0);
+    assert_eq!(diff.get_pixel(0, 0), Some(gray));
+    let red = RgbVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(0));
+    assert_eq!(diff.get_pixel(1, 1), Some(red));
+    let darker = diff.get_pixel(2, 1).unwrap();
+    assert!(darker.b().0 > 128 && darker.r().0 < 128 && darker.r() == darker.g());
+}
+
+#[test]
+fn test_resample_like() {
+    use {ScalarVal, Gray8U, GrayVal8U, Gray32F, Binary};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(4, 4);
+    for y in 0..4 {
+        for x in 0..4 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal((x * 20 + y * 50) as u8)));
+        }
+    }
+
+    let reference = ImageBufferVal::<Gray32F>::new_with_size(2, 2);
+    let small = image.resample_like(&reference, ResizeFilter::Bilinear);
+    assert_eq!((small.width(), small.height()), (2, 2));
+    assert_eq!(small.get_pixel(0, 0).unwrap().intensity(), ScalarVal(35));
+
+    let same = image.resample_like(&ImageBufferVal::<Binary>::new_with_size(4, 4),
+                                   ResizeFilter::Lanczos3);
+    for y in 0..4 {
+        for x in 0..4 {
+            assert_eq!(same.get_pixel(x, y), image.get_pixel(x, y));
+        }
+    }
+}
+
+#[test]
+fn test_quantize_median_cut() {
+    use {ScalarVal, Rgb8U, RgbVal8U};
+
+    let colors = [RgbVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(0)),
+                  RgbVal8U::new(ScalarVal(0), ScalarVal(200), ScalarVal(0)),
+                  RgbVal8U::new(ScalarVal(10), ScalarVal(20), ScalarVal(250)),
+                  RgbVal8U::new(ScalarVal(240), ScalarVal(240), ScalarVal(240))];
+    let mut image = ImageBufferVal::<Rgb8U>::new_with_size(8, 8);
+    for y in 0..8 {
+        for x in 0..8 {
+            image.set_pixel(x, y, colors[((x / 2 + y) % 4) as usize]);
+        }
+    }
+
+    let (indexed, palette) = image.quantize_median_cut(4);
+    assert_eq!(palette.len(), 4);
+    assert!(colors.iter().all(|color| palette.contains(color)));
+    for y in 0..8 {
+        for x in 0..8 {
+            let index = indexed.get_pixel(x, y).unwrap().index();
+            assert_eq!(Some(palette[index as usize]), image.get_pixel(x, y));
+        }
+    }
+
+    let (_, palette) = image.quantize_median_cut(256);
+    assert_eq!(palette.len(), 4);
+
+    let (indexed, palette) = image.quantize_median_cut(2);
+    assert_eq!(palette.len(), 2);
+    for y in 0..8 {
+        for x in 0..8 {
+            let pixel = image.get_pixel(x, y).unwrap();
+            let distance = |color: &RgbVal8U| {
+                (color.r().0 as i32 - pixel.r().0 as i32).pow(2) +
+                (color.g().0 as i32 - pixel.g().0 as i32).pow(2) +
+                (color.b().0 as i32 - pixel.b().0 as i32).pow(2)
+            };
+            let chosen = distance(&palette[indexed.get_pixel(x, y).unwrap().index() as usize]);
+            assert!(palette.iter().all(|color| chosen <= distance(color)));
+        }
+    }
+}
+
+#[test]
+fn test_palette_image() {
+    use {ScalarVal, Palette, PaletteVal, RgbVal8U};
+
+    let palette = vec![RgbVal8U::new(ScalarVal(0), ScalarVal(0), ScalarVal(0)),
+                       RgbVal8U::new(ScalarVal(255), ScalarVal(128), ScalarVal(0)),
+                       RgbVal8U::new(ScalarVal(10), ScalarVal(20), ScalarVal(30))];
+    let mut image = PaletteImage::new(ImageBufferVal::<Palette>::new_with_size(3, 2), palette);
+    assert_eq!(image.indices().get_pixel(2, 1), Some(PaletteVal::new(0)));
+    image.indices_mut().set_pixel(1, 0, PaletteVal::new(1));
+    let mut pixel = PaletteVal::new(0);
+    pixel.set_index(2);
+    image.indices_mut().set_pixel(2, 1, pixel);
+
+    let rgb = image.to_rgb();
+    assert_eq!((rgb.width(), rgb.height()), (3, 2));
+    for y in 0..2 {
+        for x in 0..3 {
+            let index = image.indices().get_pixel(x, y).unwrap().index() as usize;
+            assert_eq!(rgb.get_pixel(x, y), Some(image.palette()[index]));
+        }
+    }
+    assert_eq!(rgb.get_pixel(1, 0).unwrap().g(), ScalarVal(128));
+    assert_eq!(rgb.get_pixel(2, 1).unwrap().b(), ScalarVal(30));
+}
+
+#[test]
+#[should_panic]
+fn test_palette_image_index_out_of_range() {
+    use {Palette, PaletteVal};
+
+    let mut image = PaletteImage::new(ImageBufferVal::<Palette>::new_with_size(2, 2), Vec::new());
+    image.indices_mut().set_pixel(0, 0, PaletteVal::new(3));
+    image.to_rgb();
+}
+
+#[test]
+fn test_draw_grid() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(4, 4);
+    image.draw_grid(2, GrayVal8U::new(ScalarVal(255)));
+    for y in 0..4 {
+        for x in 0..4 {
+            let expected = if x % 2 == 0 || y % 2 == 0 { 255 } else { 0 };
+            assert_eq!(image.get_pixel(x, y).unwrap().intensity(), ScalarVal(expected));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_draw_grid_zero_spacing() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(4, 4);
+    image.draw_grid(0, GrayVal8U::new(ScalarVal(255)));
+}
+
+#[test]
+fn test_rle_round_trip() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size(100, 5);
+    for y in 0..5 {
+        for x in 0..100 {
+            let value = if y < 3 { 7 } else if x < 40 { 200 } else { (x % 2) as u8 };
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal(value)));
+        }
+    }
+
+    let data = image.rle_compress();
+    // 300 pixels of 7 need two runs, then per row one run of 200 and 60 single pixels
+    assert_eq!(&data[..6], &[255, 7, 45, 7, 40, 200]);
+    assert_eq!(data.len(), 2 * (2 + 2 * 61));
+    let restored = ImageBufferVal::rle_decompress(100, 5, &data).unwrap();
+    for y in 0..5 {
+        for x in 0..100 {
+            assert_eq!(restored.get_pixel(x, y), image.get_pixel(x, y));
+        }
+    }
+
+    let empty = ImageBufferVal::<Gray8U>::new_with_size(0, 0);
+    assert!(empty.rle_compress().is_empty());
+    assert!(ImageBufferVal::rle_decompress(0, 0, &[]).is_some());
+}
+
+#[test]
+fn test_rle_decompress_malformed() {
+    let data = [3u8, 1, 2, 9];
+    assert!(ImageBufferVal::rle_decompress(5, 1, &data).is_some());
+    // too few pixels, too many pixels, odd length and empty run
+    assert!(ImageBufferVal::rle_decompress(6, 1, &data).is_none());
+    assert!(ImageBufferVal::rle_decompress(2, 2, &data).is_none());
+    assert!(ImageBufferVal::rle_decompress(5, 1, &data[..3]).is_none());
+    assert!(ImageBufferVal::rle_decompress(5, 1, &[3, 1, 0, 4, 2, 9]).is_none());
+}
+
+#[test]
+fn test_convolve_fft() {
+    use {ScalarVal, Gray32F, GrayVal32F, Kernel};
+
+    let mut image = ImageBufferVal::<Gray32F>::new_with_size(40, 23);
+    for y in 0..23 {
+        for x in 0..40 {
+            let value = if (10..30).contains(&x) && (5..15).contains(&y) { 1.0 } else { 0.2 };
+            image.set_pixel(x, y, GrayVal32F::new(ScalarVal(value + (x * y % 7) as f32 * 0.05)));
+        }
+    }
+    // asymmetric weights make sure the kernel is flipped the same way
+    let weights: Vec<ScalarVal<f32>> = (0..15 * 15)
+        .map(|i| {
+            let (dx, dy) = ((i % 15) as f32 - 7.0, (i / 15) as f32 - 7.0);
+            ScalarVal((-(dx * dx + dy * dy) / 18.0).exp() * (1.0 + 0.02 * dx))
+        })
+        .collect();
+    let kernel = Kernel::new(15, 15, &weights);
+
+    let direct = image.convolve(&kernel, BorderMode::Zero);
+    let fast = image.convolve_fft(&kernel);
+    assert_eq!((fast.width(), fast.height()), (40, 23));
+    for y in 0..23 {
+        for x in 0..40 {
+            let a = direct.get_pixel(x, y).unwrap().intensity().0;
+            let b = fast.get_pixel(x, y).unwrap().intensity().0;
+            assert!((a - b).abs() < 1e-3, "({:}, {:}): {:} vs. {:}", x, y, a, b);
+        }
+    }
+}
+
+#[test]
+fn test_map() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size_and_pitch(3, 2, 5);
+    for y in 0..2 {
+        for x in 0..3 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal((40 * (y * 3 + x)) as u8)));
+        }
+    }
+    let inverted = image.map(|p| ScalarVal(255) - p);
+    assert_eq!((inverted.width(), inverted.height(), inverted.pitch()), (3, 2, 5));
+    for y in 0..2 {
+        for x in 0..3 {
+            let value = 255 - 40 * (y * 3 + x) as u8;
+            assert_eq!(inverted.get_pixel(x, y).unwrap().intensity(), ScalarVal(value));
+        }
+    }
+
+    image.map_in_place(|p| ScalarVal(255) - p);
+    let mut expected = [0u8; 10];
+    inverted.write_into_raw_buffer(&mut expected);
+    let mut actual = [0u8; 10];
+    image.write_into_raw_buffer(&mut actual);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_zip_with() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut a = ImageBufferVal::<Gray8U>::new_with_size(4, 2);
+    let mut b = ImageBufferVal::<Gray8U>::new_with_size(4, 2);
+    for y in 0..2 {
+        for x in 0..4 {
+            a.set_pixel(x, y, GrayVal8U::new(ScalarVal((x * 50) as u8)));
+            b.set_pixel(x, y, GrayVal8U::new(ScalarVal((200 - y * 100) as u8)));
+        }
+    }
+    let max = a.zip_with(&b, |p, q| GrayVal8U::new(p.intensity().max(q.intensity())));
+    let expected = [[200, 200, 200, 200], [100, 100, 100, 150]];
+    for y in 0..2 {
+        for x in 0..4 {
+            assert_eq!(max.get_pixel(x, y).unwrap().intensity(),
+                       ScalarVal(expected[y as usize][x as usize]));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_zip_with_size_mismatch() {
+    use Gray8U;
+
+    let a = ImageBufferVal::<Gray8U>::new_with_size(4, 2);
+    let b = ImageBufferVal::<Gray8U>::new_with_size(2, 4);
+    a.zip_with(&b, |p, _| p);
+}
+
+#[test]
+fn test_pixel_iterators() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size_and_pitch(3, 3, 4);
+    for y in 0..3 {
+        for x in 0..3 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal((x * 10 + y * 3 + 1) as u8)));
+        }
+    }
+    let mut expected = 0u32;
+    for y in 0..3 {
+        for x in 0..3 {
+            expected += image.get_pixel(x, y).unwrap().intensity().0 as u32;
+        }
+    }
+    let sum: u32 = image.pixels().map(|p| p.intensity().0 as u32).sum();
+    assert_eq!(sum, expected);
+
+    let locations: Vec<(u32, u32)> = image.enumerate_pixels().map(|(x, y, _)| (x, y)).collect();
+    assert_eq!(locations[..4], [(0, 0), (1, 0), (2, 0), (0, 1)]);
+    assert_eq!(locations.len(), 9);
+    assert!(image.enumerate_pixels().all(|(x, y, p)| image.get_pixel(x, y) == Some(p)));
+    assert_eq!(ImageBufferVal::<Gray8U>::new_with_size(0, 5).pixels().count(), 0);
+}
+
+#[test]
+fn test_pixels_mut() {
+    use {ScalarVal, Gray16U, GrayVal16U};
+
+    let mut image = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(3, 2, 8);
+    for y in 0..2 {
+        for x in 0..3 {
+            image.set_pixel(x, y, GrayVal16U::new(ScalarVal((1000 * (y * 3 + x) + 1) as u16)));
+        }
+    }
+    for mut pixel in image.pixels_mut() {
+        *pixel *= ScalarVal(2);
+    }
+    for y in 0..2 {
+        for x in 0..3 {
+            let expected = (2000 * (y * 3 + x) + 2) as u16;
+            assert_eq!(image.get_pixel(x, y).unwrap().intensity(), ScalarVal(expected));
+        }
+    }
+    assert_eq!(image.pixels_mut().count(), 6);
+    assert_eq!(ImageBufferVal::<Gray16U>::new_with_size(0, 3).pixels_mut().count(), 0);
+}
+
+#[test]
+fn test_row_bytes() {
+    use {ScalarVal, Pixel, Gray16U, GrayVal16U};
+
+    let mut image = ImageBufferVal::<Gray16U>::new_with_size(3, 2);
+    image.set_pixel(1, 1, GrayVal16U::new(ScalarVal(0x0102)));
+    let row = image.row_bytes(1).unwrap();
+    assert_eq!(row.len(), Gray16U::calc_minimum_pitch(3, 1));
+    assert_eq!(&row[2..4], &0x0102u16.to_ne_bytes());
+    assert!(image.row_bytes(2).is_none());
+
+    for (x, chunk) in image.row_bytes_mut(0).unwrap().chunks_exact_mut(2).enumerate() {
+        chunk.copy_from_slice(&(100 * x as u16 + 7).to_ne_bytes());
+    }
+    assert_eq!(image.get_pixel(2, 0).unwrap().intensity(), ScalarVal(207));
+    assert!(image.row_bytes_mut(5).is_none());
+
+    let padded = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(3, 2, 10);
+    assert_eq!(padded.row_bytes(1).unwrap().len(), 6);
+}
+
+#[test]
+fn test_as_bytes() {
+    use {ScalarVal, Gray16U, GrayVal16U};
+
+    let mut image = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(3, 2, 8);
+    assert_eq!(image.as_bytes().len(), image.get_size_in_bytes());
+    assert_eq!(image.as_bytes_mut().len(), 16);
+
+    image.as_bytes_mut()[10..12].copy_from_slice(&0x0304u16.to_ne_bytes());
+    assert_eq!(image.get_pixel(1, 1).unwrap().intensity(), ScalarVal(0x0304));
+    image.set_pixel(2, 0, GrayVal16U::new(ScalarVal(0x0506)));
+    assert_eq!(&image.as_bytes()[4..6], &0x0506u16.to_ne_bytes());
+}
+
+#[test]
+fn test_from_raw_vec() {
+    use {ScalarVal, Gray8U};
+
+    let data = vec![1u8, 2, 3, 0, 4, 5, 6, 0];
+    let image = ImageBufferVal::<Gray8U>::from_raw_vec(3, 2, 4, data.clone()).unwrap();
+    assert_eq!((image.width(), image.height(), image.pitch()), (3, 2, 4));
+    assert_eq!(image.get_pixel(2, 1).unwrap().intensity(), ScalarVal(6));
+    assert_eq!(image.as_bytes(), &data[..]);
+
+    assert!(ImageBufferVal::<Gray8U>::from_raw_vec(3, 2, 4, vec![0; 7]).is_none());
+    assert!(ImageBufferVal::<Gray8U>::from_raw_vec(3, 2, 4, vec![0; 9]).is_none());
+    assert!(ImageBufferVal::<Gray8U>::from_raw_vec(3, 2, 2, vec![0; 4]).is_none());
+}
+
+#[test]
+fn test_from_fn() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut calls = Vec::new();
+    let image = ImageBufferVal::<Gray8U>::from_fn(4, 4, |x, y| {
+        calls.push((x, y));
+        GrayVal8U::new(ScalarVal((x + y) as u8))
+    });
+    assert_eq!((image.width(), image.height(), image.pitch()), (4, 4, 4));
+    assert_eq!(image.get_pixel(0, 0).unwrap().intensity(), ScalarVal(0));
+    assert_eq!(image.get_pixel(3, 1).unwrap().intensity(), ScalarVal(4));
+    assert_eq!(image.get_pixel(2, 3).unwrap().intensity(), ScalarVal(5));
+    assert_eq!(image.get_pixel(3, 3).unwrap().intensity(), ScalarVal(6));
+    assert_eq!(calls.len(), 16);
+    assert_eq!(calls[..5], [(0, 0), (1, 0), (2, 0), (3, 0), (0, 1)]);
+}
+
+#[test]
+fn test_crop() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let image = ImageBufferVal::<Gray8U>::from_fn(4, 4, |x, y| {
+        GrayVal8U::new(ScalarVal((10 * y + x) as u8))
+    });
+    let center = image.crop(1, 1, 2, 2).unwrap();
+    assert_eq!((center.width(), center.height(), center.pitch()), (2, 2, 2));
+    let values: Vec<u8> = center.pixels().map(|p| p.intensity().0).collect();
+    assert_eq!(values, vec![11, 12, 21, 22]);
+
+    assert_eq!(image.crop(0, 0, 4, 4).unwrap().as_bytes(), image.as_bytes());
+    assert!(image.crop(3, 1, 2, 2).is_none());
+    assert!(image.crop(1, 3, 2, 2).is_none());
+    assert!(image.crop(1, 1, u32::MAX, 1).is_none());
+
+    let empty = image.crop(4, 2, 0, 2).unwrap();
+    assert_eq!((empty.width(), empty.height()), (0, 2));
+    assert!(image.crop(5, 0, 0, 0).is_none());
+}
+
+#[test]
+fn test_flip() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::new_with_size_and_pitch(3, 2, 5);
+    for y in 0..2 {
+        for x in 0..3 {
+            image.set_pixel(x, y, GrayVal8U::new(ScalarVal((10 * y + x + 1) as u8)));
+        }
+    }
+    let value = |img: &ImageBufferVal<Gray8U>, x, y| img.get_pixel(x, y).unwrap().intensity().0;
+
+    let horizontal = image.flip_horizontal();
+    assert_eq!(horizontal.pitch(), 5);
+    assert_eq!((value(&horizontal, 0, 0), value(&horizontal, 2, 0)), (3, 1));
+    assert_eq!((value(&horizontal, 0, 1), value(&horizontal, 2, 1)), (13, 11));
+    assert_eq!(value(&horizontal, 1, 1), 12);
+
+    let vertical = image.flip_vertical();
+    assert_eq!(vertical.pitch(), 5);
+    assert_eq!((value(&vertical, 0, 0), value(&vertical, 0, 1)), (11, 1));
+    assert_eq!((value(&vertical, 2, 0), value(&vertical, 2, 1)), (13, 3));
+
+    assert_eq!(horizontal.flip_horizontal().as_bytes(), image.as_bytes());
+    assert_eq!(vertical.flip_vertical().as_bytes(), image.as_bytes());
+    assert_eq!(ImageBufferVal::<Gray8U>::new_with_size(0, 0).flip_vertical().height(), 0);
+}
+
+#[test]
+fn test_transpose() {
+    use {ScalarVal, Gray16U, GrayVal16U};
+
+    let image = ImageBufferVal::<Gray16U>::from_fn(5, 3, |x, y| {
+        GrayVal16U::new(ScalarVal((1000 * y + x) as u16))
+    });
+    let transposed = image.transpose();
+    assert_eq!((transposed.width(), transposed.height(), transposed.pitch()), (3, 5, 6));
+    assert_eq!(transposed.get_pixel(2, 4).unwrap().intensity(), ScalarVal(2004));
+    assert_eq!(transposed.get_pixel(1, 0).unwrap().intensity(), ScalarVal(1000));
+
+    let back = transposed.transpose();
+    assert_eq!((back.width(), back.height()), (5, 3));
+    assert_eq!(back.as_bytes(), image.as_bytes());
+}
+
+#[test]
+fn test_resize_bilinear() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let image = ImageBufferVal::<Gray8U>::from_fn(8, 4, |x, _| {
+        GrayVal8U::new(ScalarVal((x * 30) as u8))
+    });
+    let small = image.resize_bilinear(4, 2);
+    assert_eq!((small.width(), small.height()), (4, 2));
+    // destination x maps to source x = 2 * x + 0.5
+    let values: Vec<u8> = (0..4).map(|x| small.get_pixel(x, 1).unwrap().intensity().0).collect();
+    assert_eq!(values, vec![15, 75, 135, 195]);
+    for x in 1..3 {
+        assert!(values[x - 1] < values[x] && values[x] < values[x + 1]);
+    }
+
+    // upscaling clamps at the border and rounds to the nearest integer
+    let pair = ImageBufferVal::<Gray8U>::from_fn(2, 1, |x, _| {
+        GrayVal8U::new(ScalarVal((x * 5) as u8))
+    });
+    let large = pair.resize_bilinear(4, 1);
+    let values: Vec<u8> = large.pixels().map(|p| p.intensity().0).collect();
+    assert_eq!(values, vec![0, 1, 4, 5]);
+}
+
+#[test]
+fn test_convolve_identity_and_box() {
+    use {ScalarVal, Gray32F, GrayVal32F, Kernel};
+
+    let image = ImageBufferVal::<Gray32F>::from_fn(3, 3, |x, y| {
+        GrayVal32F::new(ScalarVal((x + 3 * y) as f32))
+    });
+    let zero = ScalarVal(0.0f32);
+    let identity = Kernel::new(3, 3, &[zero, zero, zero,
+                                       zero, ScalarVal(1.0), zero,
+                                       zero, zero, zero]);
+    let boxed = Kernel::new(3, 3, &[ScalarVal(1.0f32 / 9.0); 9]);
+    for &(border, corner) in &[(BorderMode::Zero, 8.0 / 9.0),
+                               (BorderMode::Clamp, 12.0 / 9.0),
+                               (BorderMode::Wrap, 4.0)] {
+        let same = image.convolve(&identity, border);
+        assert_eq!(same.as_bytes(), image.as_bytes());
+
+        let blurred = image.convolve(&boxed, border);
+        let center = blurred.get_pixel(1, 1).unwrap().intensity().0;
+        assert!((center - 4.0).abs() < 1e-5, "{:?}: {:}", border, center);
+        let value = blurred.get_pixel(0, 0).unwrap().intensity().0;
+        assert!((value - corner).abs() < 1e-5, "{:?}: {:}", border, value);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Number of weights does not match the kernel size (3x3)")]
+fn test_kernel_size_mismatch() {
+    use {ScalarVal, Kernel};
+
+    Kernel::new(3, 3, &[ScalarVal(1.0f32); 8]);
+}
+
+#[test]
+fn test_box_blur() {
+    use {ScalarVal, Gray32F, GrayVal32F};
+
+    let mut image = ImageBufferVal::<Gray32F>::new_with_size(9, 9);
+    image.set_pixel(4, 4, GrayVal32F::new(ScalarVal(1.0)));
+    let blurred = image.box_blur(1);
+    let value = |x, y| blurred.get_pixel(x, y).unwrap().intensity().0;
+    for y in 0..9 {
+        for x in 0..9 {
+            let inside = (3..6).contains(&x) && (3..6).contains(&y);
+            let expected = if inside { 1.0 / 9.0 } else { 0.0 };
+            assert!((value(x, y) - expected).abs() < 1e-6, "({:}, {:})", x, y);
+            assert_eq!(value(x, y), value(8 - x, y));
+            assert_eq!(value(x, y), value(y, x));
+        }
+    }
+    let total: f32 = blurred.pixels().map(|p| p.intensity().0).sum();
+    assert!((total - 1.0).abs() < 1e-5);
+
+    assert_eq!(image.box_blur(0).as_bytes(), image.as_bytes());
+    // the separable implementation matches the direct mean filter
+    let mean = image.mean_filter(5, 5);
+    let boxed = image.box_blur(2);
+    for (a, b) in mean.pixels().zip(boxed.pixels()) {
+        assert!((a.intensity().0 - b.intensity().0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_median_filter() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let mut image = ImageBufferVal::<Gray8U>::from_fn(7, 5, |_, _| GrayVal8U::new(ScalarVal(100)));
+    image.set_pixel(3, 2, GrayVal8U::new(ScalarVal(255)));
+    image.set_pixel(0, 0, GrayVal8U::new(ScalarVal(0)));
+    image.set_pixel(6, 4, GrayVal8U::new(ScalarVal(255)));
+    let filtered = image.median_filter(1);
+    assert!(filtered.pixels().all(|p| p.intensity() == ScalarVal(100)));
+    assert_eq!(image.median_filter(0).as_bytes(), image.as_bytes());
+
+    // edges are preserved
+    let step = ImageBufferVal::<Gray8U>::from_fn(6, 3, |x, _| {
+        GrayVal8U::new(ScalarVal(if x < 3 { 10 } else { 200 }))
+    });
+    assert_eq!(step.median_filter(1).as_bytes(), step.as_bytes());
+}
+
+#[test]
+fn test_threshold() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let ramp = ImageBufferVal::<Gray8U>::from_fn(16, 2, |x, _| {
+        GrayVal8U::new(ScalarVal((x * 17) as u8))
+    });
+    let binary = ramp.threshold(ScalarVal(119));
+    let values: Vec<u8> = (0..16).map(|x| binary.get_pixel(x, 1).unwrap().intensity().0).collect();
+    assert_eq!(values[..8], [0; 8]);
+    assert_eq!(values[8..], [255; 8]);
+    // values equal to the threshold are not above it
+    assert_eq!(ramp.threshold(ScalarVal(136)).get_pixel(8, 0).unwrap().intensity(), ScalarVal(0));
+
+    let custom = ramp.threshold_to(ScalarVal(200), ScalarVal(7), ScalarVal(3));
+    let values: Vec<u8> = (0..16).map(|x| custom.get_pixel(x, 0).unwrap().intensity().0).collect();
+    assert_eq!(values[..12], [3; 12]);
+    assert_eq!(values[12..], [7; 4]);
+}
+
+#[test]
+fn test_intensity_statistics() {
+    use {ScalarVal, Gray8U, GrayVal8U};
+
+    let values = [10u8, 200, 30, 255, 0, 60, 70, 80, 250];
+    let image = ImageBufferVal::<Gray8U>::from_fn(3, 3, |x, y| {
+        GrayVal8U::new(ScalarVal(values[(y * 3 + x) as usize]))
+    });
+    assert_eq!(image.min_intensity(), Some(ScalarVal(0)));
+    assert_eq!(image.max_intensity(), Some(ScalarVal(255)));
+    // the sum exceeds the range of u8
+    assert_eq!(image.sum_intensity(), 955.0);
+    assert!((image.mean_intensity() - 955.0 / 9.0).abs() < 1e-12);
+    assert_eq!(image.sum_intensity(), image.total_intensity());
+    assert!((image.mean_intensity() - image.mean_stddev().0).abs() < 1e-12);
+    assert_eq!(image.sum_intensity(), image.total_intensity());
+    assert!((image.mean_intensity() - image.mean_stddev().0).abs() < 1e-12);
+
+    let empty = ImageBufferVal::<Gray8U>::new_with_size(0, 3);
+    assert_eq!(empty.min_intensity(), None);
+    assert_eq!(empty.max_intensity(), None);
+    assert_eq!(empty.sum_intensity(), 0.0);
+    assert!(empty.mean_intensity().is_nan());
+}
+
+#[test]
+fn test_normalize() {
+    use {ScalarVal, Gray16U, GrayVal16U, Rgba8U, RgbaVal8U};
+
+    let ramp = ImageBufferVal::<Gray16U>::from_fn(11, 1, |x, _| {
+        GrayVal16U::new(ScalarVal(10 + x as u16))
+    });
+    let normalized = ramp.normalize(ScalarVal(0), ScalarVal(255));
+    let values: Vec<u16> =
+        (0..11).map(|x| normalized.get_pixel(x, 0).unwrap().intensity().0).collect();
+    assert_eq!(values, [0, 26, 51, 77, 102, 128, 153, 179, 204, 230, 255]);
+
+    let inverted = ramp.normalize(ScalarVal(255), ScalarVal(0));
+    assert_eq!(inverted.get_pixel(0, 0).unwrap().intensity(), ScalarVal(255));
+    assert_eq!(inverted.get_pixel(10, 0).unwrap().intensity(), ScalarVal(0));
+
+    // a constant image maps to lo
+    let constant = ImageBufferVal::<Gray16U>::from_fn(3, 3, |_, _| GrayVal16U::new(ScalarVal(42)));
+    let normalized = constant.normalize(ScalarVal(7), ScalarVal(255));
+    assert!(normalized.pixels().all(|p| p.intensity() == ScalarVal(7)));
+
+    // the alpha channel neither affects the range nor gets rescaled
+    let rgba = ImageBufferVal::<Rgba8U>::from_fn(2, 1, |x, _| {
+        let value = ScalarVal(50 + 50 * x as u8);
+        RgbaVal8U::new(value, value, ScalarVal(75), ScalarVal(255 - x as u8))
+    });
+    let normalized = rgba.normalize(ScalarVal(0), ScalarVal(200));
+    let channels = |p: RgbaVal8U| (p.r().0, p.g().0, p.b().0, p.a().0);
+    assert_eq!(channels(normalized.get_pixel(0, 0).unwrap()), (0, 0, 100, 255));
+    assert_eq!(channels(normalized.get_pixel(1, 0).unwrap()), (200, 200, 100, 254));
+}
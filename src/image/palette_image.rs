@@ -0,0 +1,62 @@
+use {Rgb8U, RgbVal8U, Palette};
+use ImageBufferVal;
+
+/// Indexed color image, i.e. an image of [`Palette`](struct.Palette.html) indices together
+/// with the palette they refer to.
+#[derive(Clone)]
+pub struct PaletteImage {
+    indices: ImageBufferVal<Palette>,
+    palette: Vec<RgbVal8U>,
+}
+
+impl PaletteImage {
+    /// Combines an image of palette indices with its palette.
+    ///
+    /// # Panics
+    /// If the palette has more than 256 entries.
+    pub fn new(indices: ImageBufferVal<Palette>, palette: Vec<RgbVal8U>) -> PaletteImage {
+        assert!(palette.len() <= 256,
+                "Palette has {:} entries, at most 256 are supported",
+                palette.len());
+        PaletteImage { indices, palette }
+    }
+
+    /// Getter for the image of palette indices
+    pub fn indices(&self) -> &ImageBufferVal<Palette> {
+        &self.indices
+    }
+
+    /// Mutable getter for the image of palette indices
+    pub fn indices_mut(&mut self) -> &mut ImageBufferVal<Palette> {
+        &mut self.indices
+    }
+
+    /// Getter for the palette
+    pub fn palette(&self) -> &[RgbVal8U] {
+        &self.palette
+    }
+
+    /// Expands the indices into their palette colors.
+    ///
+    /// # Panics
+    /// If an index exceeds the palette.
+    pub fn to_rgb(&self) -> ImageBufferVal<Rgb8U> {
+        let width = self.indices.width();
+        let height = self.indices.height();
+        let mut result = ImageBufferVal::new_with_size(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let index = self.indices.get_pixel(x, y).unwrap().index() as usize;
+                let color = *self.palette.get(index).unwrap_or_else(|| {
+                    panic!("Palette index ({:}) at ({:}, {:}) exceeds the palette of {:} entries",
+                           index,
+                           x,
+                           y,
+                           self.palette.len())
+                });
+                result.set_pixel(x, y, color);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,22 @@
+/// Defines an axis-aligned rectangle in pixel coordinates.
+///
+/// The rectangle covers the pixels from (`x`, `y`) up to but excluding
+/// (`x + width`, `y + height`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rect {
+    /// Column of the top left pixel.
+    pub x: u32,
+    /// Row of the top left pixel.
+    pub y: u32,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+impl Rect {
+    /// Constructs a rectangle out of its top left corner and its size.
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Rect {
+        Rect { x, y, width, height }
+    }
+}
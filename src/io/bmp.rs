@@ -0,0 +1,65 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use Rgb8U;
+use ImageBufferVal;
+
+/// Size of the file header and the `BITMAPINFOHEADER`, i.e. the offset of the pixel array.
+const HEADER_SIZE: u32 = 14 + 40;
+
+/// Writes `image` as an uncompressed 24-bit BMP file.
+///
+/// The file uses a `BITMAPINFOHEADER`. Rows are stored bottom-up with the channels in blue,
+/// green, red order and every row is padded to a multiple of 4 bytes. The resolution is set to
+/// 72 DPI.
+///
+/// Returns an error of kind `InvalidInput` if the image is too large for the format.
+pub fn write_bmp<P>(image: &ImageBufferVal<Rgb8U>, path: P) -> io::Result<()>
+    where P: AsRef<Path>
+{
+    let (width, height) = (image.width(), image.height());
+    let pitch = (3 * width as u64).div_ceil(4) * 4;
+    let data_size = pitch * height as u64;
+    if width > i32::MAX as u32 || height > i32::MAX as u32 ||
+       data_size + HEADER_SIZE as u64 > u32::MAX as u64 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                  format!("Image size ({:}x{:}) exceeds the BMP limit",
+                                          width,
+                                          height)));
+    }
+    // 72 DPI in pixels per meter
+    let resolution = 2835u32;
+
+    let mut header = Vec::with_capacity(HEADER_SIZE as usize);
+    header.extend_from_slice(b"BM");
+    header.extend_from_slice(&(data_size as u32 + HEADER_SIZE).to_le_bytes());
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+    header.extend_from_slice(&40u32.to_le_bytes());
+    header.extend_from_slice(&(width as i32).to_le_bytes());
+    // a positive height marks the bottom-up row order
+    header.extend_from_slice(&(height as i32).to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&24u16.to_le_bytes());
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header.extend_from_slice(&(data_size as u32).to_le_bytes());
+    header.extend_from_slice(&resolution.to_le_bytes());
+    header.extend_from_slice(&resolution.to_le_bytes());
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header.extend_from_slice(&0u32.to_le_bytes());
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&header)?;
+    let mut row = vec![0u8; pitch as usize];
+    for y in (0..height).rev() {
+        for x in 0..width {
+            let pixel = image.get_pixel(x, y).unwrap();
+            let offset = 3 * x as usize;
+            row[offset] = pixel.b().0;
+            row[offset + 1] = pixel.g().0;
+            row[offset + 2] = pixel.r().0;
+        }
+        writer.write_all(&row)?;
+    }
+    writer.flush()
+}
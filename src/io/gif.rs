@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use PaletteImage;
+
+/// Largest number of codes in the LZW dictionary of a GIF.
+const MAX_CODES: u16 = 4096;
+
+impl PaletteImage {
+    /// Writes the image as a single frame GIF89a.
+    ///
+    /// The palette becomes the global color table, padded with black to the next power of two
+    /// (at least two entries), and the indices are LZW compressed. GIF limits the width and
+    /// height to 65535 pixels.
+    ///
+    /// Returns an error of kind `InvalidInput` if the image is too large or an index exceeds
+    /// the palette.
+    pub fn write_gif<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let indices = self.indices();
+        let (width, height) = (indices.width(), indices.height());
+        if width > u16::MAX as u32 || height > u16::MAX as u32 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("Image size ({:}x{:}) exceeds the GIF limit",
+                                              width,
+                                              height)));
+        }
+        let palette = self.palette();
+        let pixels: Vec<u8> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| indices.get_pixel(x, y).unwrap().index())
+            .collect();
+        if let Some(&index) = pixels.iter().find(|&&index| index as usize >= palette.len()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("Palette index ({:}) exceeds the palette of {:} \
+                                               entries",
+                                              index,
+                                              palette.len())));
+        }
+
+        // number of bits per index, the color table has 2^bits entries
+        let mut bits = 1;
+        while (1 << bits) < palette.len() {
+            bits += 1;
+        }
+
+        writer.write_all(b"GIF89a")?;
+        writer.write_all(&(width as u16).to_le_bytes())?;
+        writer.write_all(&(height as u16).to_le_bytes())?;
+        // global color table present, color resolution and table size, background 0, no aspect
+        writer.write_all(&[0x80 | ((bits - 1) << 4) | (bits - 1), 0, 0])?;
+        let mut table = vec![0u8; 3 << bits];
+        for (entry, color) in table.chunks_mut(3).zip(palette.iter()) {
+            entry.copy_from_slice(&[color.r().0, color.g().0, color.b().0]);
+        }
+        writer.write_all(&table)?;
+
+        // image descriptor covering the whole screen without local color table
+        writer.write_all(&[0x2c, 0, 0, 0, 0])?;
+        writer.write_all(&(width as u16).to_le_bytes())?;
+        writer.write_all(&(height as u16).to_le_bytes())?;
+        writer.write_all(&[0])?;
+
+        let min_code_size = bits.max(2);
+        writer.write_all(&[min_code_size])?;
+        for block in lzw_encode(&pixels, min_code_size).chunks(255) {
+            writer.write_all(&[block.len() as u8])?;
+            writer.write_all(block)?;
+        }
+        writer.write_all(&[0, 0x3b])
+    }
+}
+
+/// Collects variable length codes into bytes, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.count;
+        self.count += size;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compresses `data` with the GIF variant of LZW.
+///
+/// The stream starts with a clear code and ends with the end of information code. Codes
+/// grow from `min_code_size + 1` up to 12 bits, when the dictionary is full a clear code is
+/// emitted and it starts over.
+fn lzw_encode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut output = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        count: 0,
+    };
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next_code = clear + 2;
+    output.write(clear, code_size);
+
+    let mut pixels = data.iter();
+    let mut prefix = match pixels.next() {
+        Some(&first) => first as u16,
+        None => {
+            output.write(end, code_size);
+            return output.finish();
+        }
+    };
+    for &pixel in pixels {
+        if let Some(&code) = dictionary.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        output.write(prefix, code_size);
+        // the decoder adds its entries one code later, so it widens the codes already now
+        if next_code == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+        if next_code < MAX_CODES {
+            dictionary.insert((prefix, pixel), next_code);
+            next_code += 1;
+        } else {
+            output.write(clear, code_size);
+            dictionary.clear();
+            code_size = min_code_size + 1;
+            next_code = clear + 2;
+        }
+        prefix = pixel as u16;
+    }
+    output.write(prefix, code_size);
+    if next_code == 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+    output.write(end, code_size);
+    output.finish()
+}
@@ -0,0 +1,342 @@
+mod stream;
+mod png;
+mod base64;
+mod tagged;
+mod gif;
+mod netpbm;
+mod bmp;
+
+pub use self::stream::*;
+pub use self::tagged::*;
+pub use self::netpbm::*;
+pub use self::bmp::*;
+pub use self::png::PngPixel;
+pub(crate) use self::png::encode_png;
+pub(crate) use self::base64::encode_base64;
+
+#[test]
+fn test_process_rows() {
+    use Gray8U;
+
+    // two rows with 3 pixels and one padding byte each
+    let input = [0u8, 1, 2, 9, 10, 20, 30, 9];
+    let mut output = Vec::new();
+    let rows = process_rows::<Gray8U, _, _, _>(&mut &input[..], &mut output, 3, 4, |row| {
+            assert_eq!(row.len(), 3);
+            for value in row.iter_mut() {
+                *value = 255 - *value;
+            }
+        })
+        .unwrap();
+    assert_eq!(rows, 2);
+    assert_eq!(output, [255u8, 254, 253, 9, 245, 235, 225, 9]);
+
+    let truncated = [0u8, 1, 2, 9, 10, 20];
+    let mut output = Vec::new();
+    let err = process_rows::<Gray8U, _, _, _>(&mut &truncated[..], &mut output, 3, 4, |_| {})
+        .unwrap_err();
+    assert_eq!(err.kind(), ::std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_encode_base64() {
+    assert_eq!(encode_base64(b""), "");
+    assert_eq!(encode_base64(b"f"), "Zg==");
+    assert_eq!(encode_base64(b"fo"), "Zm8=");
+    assert_eq!(encode_base64(b"foo"), "Zm9v");
+    assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+}
+
+#[test]
+fn test_img_round_trip() {
+    use {ScalarVal, Gray16U, GrayVal16U, ImageBufferVal};
+
+    let mut img = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(3, 2, 8);
+    for y in 0..2 {
+        for x in 0..3 {
+            img.set_pixel(x, y, GrayVal16U::new(ScalarVal(1000 * y as u16 + 300 * x as u16 + 1)));
+        }
+    }
+    let mut data = Vec::new();
+    img.write_img(&mut data).unwrap();
+    assert_eq!(data.len(), 17 + 12);
+    // samples are stored little endian, independent of the machine
+    assert_eq!(data[17..21], [1, 0, 0x2d, 0x01]);
+
+    let read = match read_img(&mut &data[..]).unwrap() {
+        AnyImage::Gray16U(read) => read,
+        other => panic!("Unexpected pixel format {:?}", other.pixel_format()),
+    };
+    assert_eq!((read.width(), read.height(), read.pitch()), (3, 2, 8));
+    for y in 0..2 {
+        for x in 0..3 {
+            assert_eq!(read.get_pixel(x, y), img.get_pixel(x, y));
+        }
+    }
+
+    let mut corrupted = data.clone();
+    corrupted[0] = b'X';
+    let err = read_img(&mut &corrupted[..]).err().unwrap();
+    assert_eq!(err.kind(), ::std::io::ErrorKind::InvalidData);
+
+    let mut corrupted = data.clone();
+    corrupted[4] = 200;
+    let err = read_img(&mut &corrupted[..]).err().unwrap();
+    assert_eq!(err.kind(), ::std::io::ErrorKind::InvalidData);
+
+    let err = read_img(&mut &data[..data.len() - 1]).err().unwrap();
+    assert_eq!(err.kind(), ::std::io::ErrorKind::UnexpectedEof);
+
+    // huge dimensions in the header do not allocate memory for missing data
+    let mut huge = data[..17].to_vec();
+    huge[4] = PixelFormat::Gray64F.tag();
+    huge[5..9].copy_from_slice(&65535u32.to_le_bytes());
+    huge[9..13].copy_from_slice(&65535u32.to_le_bytes());
+    huge[13..17].copy_from_slice(&(8 * 65535u32).to_le_bytes());
+    let err = read_img(&mut &huge[..]).err().unwrap();
+    assert_eq!(err.kind(), ::std::io::ErrorKind::UnexpectedEof);
+
+    // a pitch which can not be allocated is an error as well
+    let mut huge = data[..17].to_vec();
+    huge[4] = PixelFormat::Gray8U.tag();
+    huge[5..9].copy_from_slice(&1u32.to_le_bytes());
+    huge[9..13].copy_from_slice(&65535u32.to_le_bytes());
+    huge[13..17].copy_from_slice(&u32::MAX.to_le_bytes());
+    huge.extend(::std::iter::repeat_n(0u8, 65535));
+    let err = read_img(&mut &huge[..]).err().unwrap();
+    assert_eq!(err.kind(), ::std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_write_gif() {
+    use {ScalarVal, Palette, PaletteVal, RgbVal8U, PaletteImage, ImageBufferVal};
+
+    // minimal GIF decoder for a single frame with global color table
+    fn decode(gif: &[u8]) -> (u16, u16, Vec<[u8; 3]>, Vec<u8>) {
+        assert_eq!(&gif[..6], b"GIF89a");
+        let read_u16 = |offset: usize| u16::from_le_bytes([gif[offset], gif[offset + 1]]);
+        let (width, height) = (read_u16(6), read_u16(8));
+        assert_eq!(gif[10] & 0x80, 0x80);
+        let table_len = 2usize << (gif[10] & 7);
+        let table: Vec<[u8; 3]> = gif[13..13 + 3 * table_len]
+            .chunks(3)
+            .map(|c| [c[0], c[1], c[2]])
+            .collect();
+        let mut pos = 13 + 3 * table_len;
+        assert_eq!(gif[pos], 0x2c);
+        assert_eq!((read_u16(pos + 5), read_u16(pos + 7), gif[pos + 9]), (width, height, 0));
+        pos += 10;
+        let min_code_size = gif[pos];
+        pos += 1;
+        let mut data = Vec::new();
+        while gif[pos] != 0 {
+            let len = gif[pos] as usize;
+            data.extend_from_slice(&gif[pos + 1..pos + 1 + len]);
+            pos += 1 + len;
+        }
+        assert_eq!(&gif[pos..], &[0, 0x3b]);
+
+        let clear = 1usize << min_code_size;
+        let mut dictionary: Vec<Vec<u8>> = Vec::new();
+        let mut code_size = min_code_size as usize + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = Vec::new();
+        let mut bit = 0;
+        loop {
+            let code = (0..code_size)
+                .map(|i| ((data[(bit + i) / 8] >> ((bit + i) % 8)) as usize & 1) << i)
+                .sum::<usize>();
+            bit += code_size;
+            if code == clear {
+                dictionary = (0..clear).map(|i| vec![i as u8]).collect();
+                dictionary.push(Vec::new());
+                dictionary.push(Vec::new());
+                code_size = min_code_size as usize + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                break;
+            }
+            let entry = match previous {
+                None => dictionary[code].clone(),
+                Some(ref prev) => {
+                    let entry = if code < dictionary.len() {
+                        dictionary[code].clone()
+                    } else {
+                        let mut entry = prev.clone();
+                        entry.push(prev[0]);
+                        entry
+                    };
+                    if dictionary.len() < 4096 {
+                        let mut new = prev.clone();
+                        new.push(entry[0]);
+                        dictionary.push(new);
+                        if dictionary.len() == 1 << code_size && code_size < 12 {
+                            code_size += 1;
+                        }
+                    }
+                    entry
+                }
+            };
+            output.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+        (width, height, table, output)
+    }
+
+    let palette = vec![RgbVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(0)),
+                       RgbVal8U::new(ScalarVal(0), ScalarVal(255), ScalarVal(0)),
+                       RgbVal8U::new(ScalarVal(0), ScalarVal(0), ScalarVal(255))];
+    let mut indices = ImageBufferVal::<Palette>::new_with_size(5, 4);
+    for y in 0..4 {
+        for x in 0..5 {
+            indices.set_pixel(x, y, PaletteVal::new(((x + y) % 3) as u8));
+        }
+    }
+    let image = PaletteImage::new(indices, palette);
+    let mut gif = Vec::new();
+    image.write_gif(&mut gif).unwrap();
+    let (width, height, table, pixels) = decode(&gif);
+    assert_eq!((width, height), (5, 4));
+    assert_eq!(&table[..], &[[255, 0, 0], [0, 255, 0], [0, 0, 255], [0, 0, 0]]);
+    let expected: Vec<u8> = (0..4).flat_map(|y| (0..5).map(move |x| ((x + y) % 3) as u8)).collect();
+    assert_eq!(pixels, expected);
+
+    // enough noisy pixels to grow the codes to 12 bits and to fill the dictionary
+    let palette = (0..200)
+        .map(|i| RgbVal8U::new(ScalarVal(i), ScalarVal(0), ScalarVal(0)))
+        .collect();
+    let mut indices = ImageBufferVal::<Palette>::new_with_size(300, 200);
+    let mut state = 1u32;
+    let mut expected = Vec::new();
+    for y in 0..200 {
+        for x in 0..300 {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            let index = ((state >> 16) % 200) as u8;
+            indices.set_pixel(x, y, PaletteVal::new(index));
+            expected.push(index);
+        }
+    }
+    let mut gif = Vec::new();
+    PaletteImage::new(indices, palette).write_gif(&mut gif).unwrap();
+    let (width, height, table, pixels) = decode(&gif);
+    assert_eq!((width, height, table.len()), (300, 200, 256));
+    assert_eq!(pixels, expected);
+
+    let image = PaletteImage::new(ImageBufferVal::<Palette>::new_with_size(2, 2), Vec::new());
+    assert_eq!(image.write_gif(&mut Vec::new()).unwrap_err().kind(),
+               ::std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_pgm_round_trip() {
+    use {ScalarVal, Gray8U, Gray16U, GrayVal8U, GrayVal16U, ImageBufferVal};
+
+    let path = ::std::env::temp_dir().join(format!("img_test_pgm_{:}.pgm", ::std::process::id()));
+
+    let gradient = ImageBufferVal::<Gray8U>::from_fn(4, 3, |x, y| {
+        GrayVal8U::new(ScalarVal((x * 60 + y) as u8))
+    });
+    write_pgm(&gradient, &path).unwrap();
+    let data = ::std::fs::read(&path).unwrap();
+    assert_eq!(&data[..11], b"P5\n4 3\n255\n");
+    assert_eq!(data.len(), 11 + 12);
+    let read: ImageBufferVal<Gray8U> = read_pgm(&path).unwrap();
+    assert_eq!((read.width(), read.height()), (4, 3));
+    assert!(read.enumerate_pixels().all(|(x, y, p)| Some(p) == gradient.get_pixel(x, y)));
+    // the maxval requires the other pixel type
+    assert_eq!(read_pgm::<Gray16U, _>(&path).err().unwrap().kind(),
+               ::std::io::ErrorKind::InvalidData);
+
+    // samples are stored big endian, the padding of the image is not written
+    let mut gradient = ImageBufferVal::<Gray16U>::new_with_size_and_pitch(2, 2, 6);
+    for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        gradient.set_pixel(x, y, GrayVal16U::new(ScalarVal((x * 0x1000 + y * 0x0102) as u16)));
+    }
+    write_pgm(&gradient, &path).unwrap();
+    let data = ::std::fs::read(&path).unwrap();
+    assert_eq!(&data[13..], &[0x00, 0x00, 0x10, 0x00, 0x01, 0x02, 0x11, 0x02]);
+    let read: ImageBufferVal<Gray16U> = read_pgm(&path).unwrap();
+    assert!(read.enumerate_pixels().all(|(x, y, p)| Some(p) == gradient.get_pixel(x, y)));
+
+    // comments and arbitrary whitespace in the header
+    ::std::fs::write(&path, b"P5 # comment\n2\t1 # another\n 15\n\x07\x0f").unwrap();
+    let read: ImageBufferVal<Gray8U> = read_pgm(&path).unwrap();
+    assert_eq!(read.get_pixel(0, 0), Some(GrayVal8U::new(ScalarVal(7))));
+    assert_eq!(read.get_pixel(1, 0), Some(GrayVal8U::new(ScalarVal(15))));
+
+    for &(data, kind) in &[(&b"P6\n2 1\n255\n\x00\x00"[..], ::std::io::ErrorKind::InvalidData),
+                           (&b"P5\n2 x\n255\n\x00\x00"[..], ::std::io::ErrorKind::InvalidData),
+                           (&b"P5\n2 1\n0\n\x00\x00"[..], ::std::io::ErrorKind::InvalidData),
+                           (&b"P5\n2 1\n255\n\x00"[..], ::std::io::ErrorKind::UnexpectedEof),
+                           (&b"P5\n2 1"[..], ::std::io::ErrorKind::UnexpectedEof)] {
+        ::std::fs::write(&path, data).unwrap();
+        assert_eq!(read_pgm::<Gray8U, _>(&path).err().unwrap().kind(), kind);
+    }
+    ::std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_ppm_round_trip() {
+    use {ScalarVal, Rgb8U, RgbVal8U, ImageBufferVal};
+
+    let path = ::std::env::temp_dir().join(format!("img_test_ppm_{:}.ppm", ::std::process::id()));
+
+    let mut image = ImageBufferVal::<Rgb8U>::new_with_size_and_pitch(2, 2, 8);
+    image.set_pixel(0, 0, RgbVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(0)));
+    image.set_pixel(1, 0, RgbVal8U::new(ScalarVal(0), ScalarVal(255), ScalarVal(0)));
+    image.set_pixel(0, 1, RgbVal8U::new(ScalarVal(0), ScalarVal(0), ScalarVal(255)));
+    image.set_pixel(1, 1, RgbVal8U::new(ScalarVal(1), ScalarVal(2), ScalarVal(3)));
+    write_ppm(&image, &path).unwrap();
+    let data = ::std::fs::read(&path).unwrap();
+    assert_eq!(&data[..11], b"P6\n2 2\n255\n");
+    assert_eq!(&data[11..], &[255, 0, 0, 0, 255, 0, 0, 0, 255, 1, 2, 3]);
+
+    let read = read_ppm(&path).unwrap();
+    assert_eq!((read.width(), read.height(), read.pitch()), (2, 2, 6));
+    assert!(read.enumerate_pixels().all(|(x, y, p)| Some(p) == image.get_pixel(x, y)));
+
+    for &(data, kind) in &[(&b"P5\n1 1\n255\n\x00\x00\x00"[..], ::std::io::ErrorKind::InvalidData),
+                           (&b"P6\n1\n255\n\x00\x00\x00"[..], ::std::io::ErrorKind::InvalidData),
+                           (&b"P6\n-1 1\n255\n\x00\x00\x00"[..], ::std::io::ErrorKind::InvalidData),
+                           (&b"P6\n1 1\n256\n\x00\x00\x00"[..], ::std::io::ErrorKind::InvalidData),
+                           (&b"P6\n1 1\n255\n\x00\x00"[..], ::std::io::ErrorKind::UnexpectedEof),
+                           (&b"P6\n1 1\n"[..], ::std::io::ErrorKind::UnexpectedEof)] {
+        ::std::fs::write(&path, data).unwrap();
+        assert_eq!(read_ppm(&path).err().unwrap().kind(), kind);
+    }
+    ::std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_write_bmp() {
+    use {ScalarVal, Rgb8U, RgbVal8U, ImageBufferVal};
+
+    let path = ::std::env::temp_dir().join(format!("img_test_bmp_{:}.bmp", ::std::process::id()));
+
+    // 3 pixels per row need 9 bytes, padded to 12
+    let image = ImageBufferVal::<Rgb8U>::from_fn(3, 2, |x, y| {
+        RgbVal8U::new(ScalarVal((10 * x + y) as u8), ScalarVal(100 + y as u8), ScalarVal(200))
+    });
+    write_bmp(&image, &path).unwrap();
+    let data = ::std::fs::read(&path).unwrap();
+    ::std::fs::remove_file(&path).unwrap();
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let read_u32 = |offset: usize| {
+        u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+    };
+    assert_eq!(&data[..2], b"BM");
+    assert_eq!(read_u32(2) as usize, data.len());
+    assert_eq!(data.len(), 54 + 2 * 12);
+    assert_eq!(read_u32(10), 54);
+    assert_eq!(read_u32(14), 40);
+    assert_eq!((read_u32(18), read_u32(22)), (3, 2));
+    assert_eq!((read_u16(26), read_u16(28)), (1, 24));
+    assert_eq!((read_u32(30), read_u32(34)), (0, 24));
+
+    // bottom-up rows in blue, green, red order
+    assert_eq!(&data[54..66], &[200, 101, 1, 200, 101, 11, 200, 101, 21, 0, 0, 0]);
+    assert_eq!(&data[66..], &[200, 100, 0, 200, 100, 10, 200, 100, 20, 0, 0, 0]);
+}
@@ -0,0 +1,181 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use {Pixel, Gray8U, Gray16U, Rgb8U, Endianness};
+use ImageBufferVal;
+
+/// Pixel types which can be stored in a binary PGM file, see [`read_pgm`](fn.read_pgm.html).
+pub trait PgmPixel: Pixel {
+    /// The maxval written into the header of a file with this pixel type.
+    const MAX_VALUE: u16;
+}
+
+impl PgmPixel for Gray8U {
+    const MAX_VALUE: u16 = 255;
+}
+impl PgmPixel for Gray16U {
+    const MAX_VALUE: u16 = 65535;
+}
+
+/// Reads a binary PGM (`P5`) file.
+///
+/// Files with a maxval up to 255 store one byte per sample and have to be read as
+/// [`Gray8U`](type.Gray8U.html), larger maxvals store two big endian bytes per sample and have
+/// to be read as [`Gray16U`](type.Gray16U.html). The samples are not rescaled, i.e. they are in
+/// the range `0..=maxval` of the file.
+///
+/// Returns an error of kind `InvalidData` if the header is malformed or if the maxval does not
+/// match `PixelP` and of kind `UnexpectedEof` if the data is truncated.
+pub fn read_pgm<PixelP, P>(path: P) -> io::Result<ImageBufferVal<PixelP>>
+    where PixelP: PgmPixel,
+          P: AsRef<Path>
+{
+    let data = fs::read(path)?;
+    let header = parse_header(&data, b"P5")?;
+    if (header.maxval > 255) != (PixelP::MAX_VALUE > 255) {
+        return Err(invalid_data(format!("Maxval ({:}) does not match the pixel type",
+                                        header.maxval)));
+    }
+    read_samples(&data[header.offset..], header.width, header.height)
+}
+
+/// Writes `image` as a binary PGM (`P5`) file.
+///
+/// The maxval is the largest value of the pixel type, i.e. `255` for
+/// [`Gray8U`](type.Gray8U.html) and `65535` for [`Gray16U`](type.Gray16U.html). Use
+/// [`read_pgm`](fn.read_pgm.html) to read it back.
+pub fn write_pgm<PixelP, P>(image: &ImageBufferVal<PixelP>, path: P) -> io::Result<()>
+    where PixelP: PgmPixel,
+          P: AsRef<Path>
+{
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "P5\n{:} {:}\n{:}", image.width(), image.height(), PixelP::MAX_VALUE)?;
+    writer.write_all(&samples(image))?;
+    writer.flush()
+}
+
+/// Reads a binary PPM (`P6`) file with a maxval up to 255.
+///
+/// The image has the minimum pitch. The samples are not rescaled, i.e. they are in the range
+/// `0..=maxval` of the file.
+///
+/// Returns an error of kind `InvalidData` if the header is malformed or if the maxval is larger
+/// than 255 and of kind `UnexpectedEof` if the data is truncated.
+pub fn read_ppm<P>(path: P) -> io::Result<ImageBufferVal<Rgb8U>>
+    where P: AsRef<Path>
+{
+    let data = fs::read(path)?;
+    let header = parse_header(&data, b"P6")?;
+    if header.maxval > 255 {
+        return Err(invalid_data(format!("Maxval ({:}) exceeds the range of Rgb8U",
+                                        header.maxval)));
+    }
+    read_samples(&data[header.offset..], header.width, header.height)
+}
+
+/// Writes `image` as a binary PPM (`P6`) file with a maxval of 255.
+///
+/// Use [`read_ppm`](fn.read_ppm.html) to read it back.
+pub fn write_ppm<P>(image: &ImageBufferVal<Rgb8U>, path: P) -> io::Result<()>
+    where P: AsRef<Path>
+{
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "P6\n{:} {:}\n255", image.width(), image.height())?;
+    writer.write_all(&samples(image))?;
+    writer.flush()
+}
+
+/// The fields of a NetPBM header and the offset of the samples behind it.
+struct Header {
+    width: u32,
+    height: u32,
+    maxval: u32,
+    offset: usize,
+}
+
+/// Parses the header of a binary NetPBM file starting with `magic`.
+///
+/// Width, height and maxval are separated by whitespace and comments, which start with `#` and
+/// extend to the end of the line. A single whitespace character ends the header.
+fn parse_header(data: &[u8], magic: &[u8; 2]) -> io::Result<Header> {
+    if !data.starts_with(magic) {
+        return Err(invalid_data("Invalid magic bytes".to_string()));
+    }
+    let mut pos = magic.len();
+    let mut fields = [0u32; 3];
+    for field in fields.iter_mut() {
+        let separator = pos;
+        loop {
+            match data.get(pos) {
+                Some(&b'#') => {
+                    while data.get(pos).is_some_and(|&b| b != b'\n') {
+                        pos += 1;
+                    }
+                }
+                Some(b) if b.is_ascii_whitespace() => pos += 1,
+                _ => break,
+            }
+        }
+        let start = pos;
+        while data.get(pos).is_some_and(u8::is_ascii_digit) {
+            pos += 1;
+        }
+        if pos == data.len() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated header"));
+        }
+        if separator == start || start == pos {
+            return Err(invalid_data("Malformed header".to_string()));
+        }
+        *field = ::std::str::from_utf8(&data[start..pos])
+            .unwrap()
+            .parse()
+            .map_err(|_| invalid_data("Header value out of range".to_string()))?;
+    }
+    if !data[pos].is_ascii_whitespace() {
+        return Err(invalid_data("Malformed header".to_string()));
+    }
+    let [width, height, maxval] = fields;
+    if maxval == 0 || maxval > 65535 {
+        return Err(invalid_data(format!("Invalid maxval ({:})", maxval)));
+    }
+    Ok(Header {
+        width,
+        height,
+        maxval,
+        offset: pos + 1,
+    })
+}
+
+/// Loads an image with the minimum pitch out of big endian samples without padding.
+fn read_samples<PixelP>(samples: &[u8],
+                        width: u32,
+                        height: u32)
+                        -> io::Result<ImageBufferVal<PixelP>>
+    where PixelP: Pixel
+{
+    let size = PixelP::calc_minimum_pitch(width, 1)
+        .checked_mul(height as usize)
+        .ok_or_else(|| invalid_data(format!("Image size ({:}x{:}) is too large", width, height)))?;
+    if samples.len() < size {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated samples"));
+    }
+    let mut image = ImageBufferVal::<PixelP>::new_with_size(width, height);
+    image.load_from_raw_buffer_with_endianness(&samples[..size], Endianness::Big);
+    Ok(image)
+}
+
+/// Stores all pixels as big endian samples without padding.
+fn samples<PixelP>(image: &ImageBufferVal<PixelP>) -> Vec<u8>
+    where PixelP: Pixel
+{
+    let pitch = PixelP::calc_minimum_pitch(image.width(), 1);
+    let mut data = vec![0u8; pitch * image.height() as usize];
+    for (x, y, pixel) in image.enumerate_pixels() {
+        pixel.write_into_raw_buffer_with_endianness(x, y, pitch as u32, &mut data, Endianness::Big);
+    }
+    data
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
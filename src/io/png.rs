@@ -0,0 +1,85 @@
+use {PixelArithmetic, Gray8U, Rgb8U};
+
+/// Pixel types which can be encoded as PNG, see
+/// [`ImageVal::to_png_data_url`](struct.ImageVal.html#method.to_png_data_url).
+pub trait PngPixel: PixelArithmetic<ScalarT = u8> {
+    /// The PNG color type, the channels are stored in the order of their index.
+    const COLOR_TYPE: u8;
+}
+
+impl PngPixel for Gray8U {
+    const COLOR_TYPE: u8 = 0;
+}
+impl PngPixel for Rgb8U {
+    const COLOR_TYPE: u8 = 2;
+}
+
+/// Encodes 8 bit image data as PNG without compression.
+///
+/// `color_type` is the PNG color type (0 for gray, 2 for RGB, 6 for RGBA) and `rows` holds
+/// `height` rows of tightly packed pixel bytes. The image data is stored in uncompressed
+/// deflate blocks, which keeps the encoder small at the cost of file size.
+pub(crate) fn encode_png(width: u32, height: u32, color_type: u8, rows: &[u8]) -> Vec<u8> {
+    let row_len = if height == 0 { 0 } else { rows.len() / height as usize };
+    assert_eq!(row_len * height as usize, rows.len());
+
+    // every row is prefixed with filter type 0 (none)
+    let mut raw = Vec::with_capacity(rows.len() + height as usize);
+    for row in rows.chunks(row_len.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        zlib.push(if blocks.peek().is_none() { 1 } else { 0 });
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(kind.iter().chain(data.iter()));
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32<'a, I: Iterator<Item = &'a u8>>(bytes: I) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
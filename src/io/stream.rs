@@ -0,0 +1,74 @@
+use std::io::{self, Read, Write};
+use Pixel;
+
+/// Processes raw image data row by row, without loading the whole image into memory.
+///
+/// Rows of `pitch` bytes are read from `reader` until it is exhausted. For every row `f` is
+/// called with the bytes holding the `width` pixels of type `PixelP` (padding bytes are
+/// excluded), it can modify them in place. Afterwards the complete row including the
+/// unchanged padding is written to `writer`.
+///
+/// Returns the number of processed rows. If the data ends in the middle of a row, an error of
+/// kind `UnexpectedEof` is returned.
+///
+/// # Examples
+/// ```
+/// use img::{process_rows, Gray8U};
+/// let input = [1u8, 2, 3, 4, 5, 6];
+/// let mut output = Vec::new();
+/// let rows = process_rows::<Gray8U, _, _, _>(&mut &input[..], &mut output, 3, 3, |row| {
+///     for value in row.iter_mut() {
+///         *value *= 2;
+///     }
+/// }).unwrap();
+/// assert_eq!(rows, 2);
+/// assert_eq!(output, [2, 4, 6, 8, 10, 12]);
+/// ```
+///
+/// # Panics
+/// If `pitch` is too small for `width` pixels of type `PixelP`.
+pub fn process_rows<PixelP, R, W, F>(reader: &mut R,
+                                     writer: &mut W,
+                                     width: u32,
+                                     pitch: u32,
+                                     mut f: F)
+                                     -> io::Result<u32>
+    where PixelP: Pixel,
+          R: Read,
+          W: Write,
+          F: FnMut(&mut [u8])
+{
+    let row_size = PixelP::calc_minimum_pitch(width, 1);
+    assert!(row_size <= pitch as usize,
+            "Pitch ({:}) is too small for a width of {:}",
+            pitch,
+            width);
+
+    let mut row = vec![0u8; pitch as usize];
+    let mut rows = 0;
+    loop {
+        let mut filled = 0;
+        while filled < row.len() {
+            match reader.read(&mut row[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if filled == 0 {
+            return Ok(rows);
+        }
+        if filled < row.len() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                      format!("Row {:} ends after {:} of {:} bytes",
+                                              rows,
+                                              filled,
+                                              row.len())));
+        }
+
+        f(&mut row[..row_size]);
+        writer.write_all(&row)?;
+        rows += 1;
+    }
+}
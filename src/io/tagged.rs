@@ -0,0 +1,213 @@
+use std::io::{self, Read, Write};
+use {Pixel, Endianness, Gray8U, Gray16U, Gray32U, Gray32F, Gray64F, Binary};
+use ImageBufferVal;
+
+const MAGIC: [u8; 4] = *b"PIMG";
+
+/// Identifies the pixel type of an image in the format written by
+/// [`ImageBufferVal::write_img`](struct.ImageVal.html#method.write_img).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// [`Gray8U`](type.Gray8U.html)
+    Gray8U,
+    /// [`Gray16U`](type.Gray16U.html)
+    Gray16U,
+    /// [`Gray32U`](type.Gray32U.html)
+    Gray32U,
+    /// [`Gray32F`](type.Gray32F.html)
+    Gray32F,
+    /// [`Gray64F`](type.Gray64F.html)
+    Gray64F,
+    /// [`Binary`](struct.Binary.html)
+    Binary,
+}
+
+impl PixelFormat {
+    /// Returns the tag byte which represents this format in a file.
+    pub fn tag(self) -> u8 {
+        match self {
+            PixelFormat::Gray8U => 1,
+            PixelFormat::Gray16U => 2,
+            PixelFormat::Gray32U => 3,
+            PixelFormat::Gray32F => 4,
+            PixelFormat::Gray64F => 5,
+            PixelFormat::Binary => 6,
+        }
+    }
+
+    /// Returns the format represented by `tag`, or `None` for unknown tags.
+    pub fn from_tag(tag: u8) -> Option<PixelFormat> {
+        match tag {
+            1 => Some(PixelFormat::Gray8U),
+            2 => Some(PixelFormat::Gray16U),
+            3 => Some(PixelFormat::Gray32U),
+            4 => Some(PixelFormat::Gray32F),
+            5 => Some(PixelFormat::Gray64F),
+            6 => Some(PixelFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Pixel types which can be written with
+/// [`ImageBufferVal::write_img`](struct.ImageVal.html#method.write_img).
+pub trait HasPixelFormat: Pixel {
+    /// The format tag of this pixel type.
+    const FORMAT: PixelFormat;
+}
+
+impl HasPixelFormat for Gray8U {
+    const FORMAT: PixelFormat = PixelFormat::Gray8U;
+}
+impl HasPixelFormat for Gray16U {
+    const FORMAT: PixelFormat = PixelFormat::Gray16U;
+}
+impl HasPixelFormat for Gray32U {
+    const FORMAT: PixelFormat = PixelFormat::Gray32U;
+}
+impl HasPixelFormat for Gray32F {
+    const FORMAT: PixelFormat = PixelFormat::Gray32F;
+}
+impl HasPixelFormat for Gray64F {
+    const FORMAT: PixelFormat = PixelFormat::Gray64F;
+}
+impl HasPixelFormat for Binary {
+    const FORMAT: PixelFormat = PixelFormat::Binary;
+}
+
+/// An image of any of the pixel types known to [`PixelFormat`](enum.PixelFormat.html), as
+/// returned by [`read_img`](fn.read_img.html).
+#[derive(Clone)]
+#[allow(missing_docs)]
+pub enum AnyImage {
+    Gray8U(ImageBufferVal<Gray8U>),
+    Gray16U(ImageBufferVal<Gray16U>),
+    Gray32U(ImageBufferVal<Gray32U>),
+    Gray32F(ImageBufferVal<Gray32F>),
+    Gray64F(ImageBufferVal<Gray64F>),
+    Binary(ImageBufferVal<Binary>),
+}
+
+impl AnyImage {
+    /// Returns the pixel format of the contained image.
+    pub fn pixel_format(&self) -> PixelFormat {
+        match *self {
+            AnyImage::Gray8U(_) => PixelFormat::Gray8U,
+            AnyImage::Gray16U(_) => PixelFormat::Gray16U,
+            AnyImage::Gray32U(_) => PixelFormat::Gray32U,
+            AnyImage::Gray32F(_) => PixelFormat::Gray32F,
+            AnyImage::Gray64F(_) => PixelFormat::Gray64F,
+            AnyImage::Binary(_) => PixelFormat::Binary,
+        }
+    }
+}
+
+impl<PixelP> ImageBufferVal<PixelP>
+    where PixelP: HasPixelFormat
+{
+    /// Writes the image in a simple self-describing binary format.
+    ///
+    /// The format consists of the magic bytes `PIMG`, the [tag](enum.PixelFormat.html#method.tag)
+    /// of the pixel type, width, height and pitch as little endian `u32` and finally the
+    /// pixel data without padding, i.e. `height * calc_minimum_pitch(width, 1)` bytes with
+    /// little endian samples. Therefore the files do not depend on the byte order of the
+    /// machine. Use [`read_img`](fn.read_img.html) to read it back.
+    pub fn write_img<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[PixelP::FORMAT.tag()])?;
+        writer.write_all(&self.width().to_le_bytes())?;
+        writer.write_all(&self.height().to_le_bytes())?;
+        writer.write_all(&self.pitch().to_le_bytes())?;
+        let row_size = PixelP::calc_minimum_pitch(self.width(), 1);
+        let mut row = vec![0u8; row_size];
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self.get_pixel(x, y)
+                    .unwrap()
+                    .write_into_raw_buffer_with_endianness(x,
+                                                           0,
+                                                           row_size as u32,
+                                                           &mut row,
+                                                           Endianness::Little);
+            }
+            writer.write_all(&row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads an image written by [`ImageBufferVal::write_img`](struct.ImageVal.html#method.write_img).
+///
+/// Returns an error of kind `InvalidData` if the magic bytes, the pixel format tag or the
+/// dimensions are invalid or if the image is too large to be allocated, and of kind
+/// `UnexpectedEof` if the data is truncated. Memory is only allocated for pixel data which is
+/// actually present in `reader`.
+pub fn read_img<R: Read>(reader: &mut R) -> io::Result<AnyImage> {
+    let mut header = [0u8; 17];
+    reader.read_exact(&mut header)?;
+    if header[..4] != MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid magic bytes"));
+    }
+    let format = PixelFormat::from_tag(header[4])
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData,
+                           format!("Unknown pixel format tag ({:})", header[4]))
+        })?;
+    let read_u32 = |offset: usize| {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&header[offset..offset + 4]);
+        u32::from_le_bytes(bytes)
+    };
+    let (width, height, pitch) = (read_u32(5), read_u32(9), read_u32(13));
+
+    Ok(match format {
+        PixelFormat::Gray8U => AnyImage::Gray8U(read_pixels(reader, width, height, pitch)?),
+        PixelFormat::Gray16U => AnyImage::Gray16U(read_pixels(reader, width, height, pitch)?),
+        PixelFormat::Gray32U => AnyImage::Gray32U(read_pixels(reader, width, height, pitch)?),
+        PixelFormat::Gray32F => AnyImage::Gray32F(read_pixels(reader, width, height, pitch)?),
+        PixelFormat::Gray64F => AnyImage::Gray64F(read_pixels(reader, width, height, pitch)?),
+        PixelFormat::Binary => AnyImage::Binary(read_pixels(reader, width, height, pitch)?),
+    })
+}
+
+fn read_pixels<PixelP, R>(reader: &mut R,
+                          width: u32,
+                          height: u32,
+                          pitch: u32)
+                          -> io::Result<ImageBufferVal<PixelP>>
+    where PixelP: Pixel,
+          R: Read
+{
+    let invalid_size = || {
+        io::Error::new(io::ErrorKind::InvalidData,
+                       format!("Invalid combination of width ({:}), height ({:}) and pitch ({:})",
+                               width,
+                               height,
+                               pitch))
+    };
+    let size = PixelP::calc_size_in_bytes(width, height, pitch).ok_or_else(invalid_size)?;
+    let row_size = PixelP::calc_minimum_pitch(width, 1);
+    let data_size = row_size.checked_mul(height as usize).ok_or_else(invalid_size)?;
+
+    // the buffer only grows with the data which is actually read
+    let mut data = Vec::new();
+    reader.take(data_size as u64).read_to_end(&mut data)?;
+    if data.len() < data_size {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated pixel data"));
+    }
+
+    let mut raw_data = Vec::new();
+    raw_data.try_reserve_exact(size).map_err(|_| invalid_size())?;
+    raw_data.resize(size, 0);
+    for y in 0..height {
+        for x in 0..width {
+            PixelP::load_from_raw_buffer_with_endianness(x,
+                                                         y,
+                                                         row_size as u32,
+                                                         &data,
+                                                         Endianness::Little)
+                .write_into_raw_buffer(x, y, pitch, &mut raw_data);
+        }
+    }
+    ImageBufferVal::from_raw_vec(width, height, pitch, raw_data).ok_or_else(invalid_size)
+}
@@ -0,0 +1,66 @@
+use {Scalar, ScalarVal};
+
+/// Defines a two dimensional filter kernel.
+///
+/// The weights are stored in row-major order. Both dimensions have to be odd, so that the
+/// kernel has a well defined center at (`width / 2`, `height / 2`).
+///
+/// # Examples
+/// ```
+/// use img::{Kernel, ScalarVal};
+/// let kernel = Kernel::new(3, 1, &[ScalarVal(1.0f32), ScalarVal(2.0), ScalarVal(1.0)]);
+/// assert_eq!(kernel.get(1, 0), ScalarVal(2.0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kernel<ScalarP>
+    where ScalarP: Scalar
+{
+    width: u32,
+    height: u32,
+    weights: Vec<ScalarP>,
+}
+
+impl<ScalarP> Kernel<ScalarP>
+    where ScalarP: Scalar
+{
+    /// Constructs a kernel with the given size out of row-major `weights`.
+    ///
+    /// # Panics
+    /// If `width` or `height` is not odd or if the number of weights does not match
+    /// `width * height`.
+    pub fn new(width: u32, height: u32, weights: &[ScalarVal<ScalarP>]) -> Kernel<ScalarP> {
+        assert!(width % 2 == 1 && height % 2 == 1,
+                "Kernel size ({:}x{:}) has to be odd",
+                width,
+                height);
+        assert_eq!((width as usize) * (height as usize),
+                   weights.len(),
+                   "Number of weights does not match the kernel size ({:}x{:})",
+                   width,
+                   height);
+        Kernel {
+            width,
+            height,
+            weights: weights.iter().map(|w| w.0).collect(),
+        }
+    }
+
+    /// Returns the width of the kernel.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the kernel.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the weight at location (`x`, `y`).
+    ///
+    /// # Panics
+    /// If the location is out of bounds.
+    pub fn get(&self, x: u32, y: u32) -> ScalarVal<ScalarP> {
+        assert!(x < self.width && y < self.height);
+        ScalarVal(self.weights[(y * self.width + x) as usize])
+    }
+}
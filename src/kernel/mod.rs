@@ -0,0 +1,3 @@
+mod impl_core;
+
+pub use self::impl_core::*;
@@ -2,14 +2,40 @@
 
 //! This crate provides basic functionality and interfaces to work with images.
 
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+
 mod scalar;
 mod px;
+mod kernel;
 mod image;
+mod io;
 
-pub use scalar::{Scalar, ScalarVal};
+pub use scalar::{Scalar, ScalarVal, Endianness};
 
-pub use px::{Pixel, PixelArithmetic, PixelVal};
+pub use px::{Pixel, PixelArithmetic, PixelCheckedArithmetic, PixelVal};
 pub use px::{Gray, Gray8U, Gray16U, Gray32U, Gray32F, Gray64F};
 pub use px::{GrayVal, GrayVal8U, GrayVal16U, GrayVal32U, GrayVal32F, GrayVal64F};
+pub use px::{Gray64U, Gray8I, Gray16I, Gray32I, Gray64I};
+pub use px::{GrayVal64U, GrayVal8I, GrayVal16I, GrayVal32I, GrayVal64I};
+pub use px::{Rgb, Rgb8U, Rgb16U, Rgb32F};
+pub use px::{RgbVal, RgbVal8U, RgbVal16U, RgbVal32F};
+pub use px::{Rgba, Rgba8U, Rgba16U};
+pub use px::{RgbaVal, RgbaVal8U, RgbaVal16U};
+pub use px::{Complex, Complex32F, Complex64F};
+pub use px::{ComplexVal, ComplexVal32F, ComplexVal64F};
+pub use px::{Binary, BinaryVal};
+pub use px::{Channels, ChannelsVal};
+pub use px::{Palette, PaletteVal};
+pub use px::{SaturatingGray, SaturatingGray8U, SaturatingGray16U, SaturatingGray32U};
+pub use px::{SaturatingGrayVal, SaturatingGrayVal8U, SaturatingGrayVal16U, SaturatingGrayVal32U};
+
+pub use kernel::Kernel;
+
+pub use image::{Image, ImageVal, ImageBuffer, ImageBufferVal, PixelMut, BorderMode};
+pub use image::{PaletteImage, Rect, RegionStat, Moments, ResizeFilter, BayerPattern};
+pub use image::{stack_mean, stack_median};
 
-pub use image::{Image, ImageVal, ImageBuffer, ImageBufferVal};
+pub use io::process_rows;
+pub use io::{PixelFormat, HasPixelFormat, AnyImage, read_img, PngPixel};
+pub use io::{PgmPixel, read_pgm, write_pgm, read_ppm, write_ppm, write_bmp};
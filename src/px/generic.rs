@@ -1,5 +1,5 @@
 use std::fmt::Debug;
-use Scalar;
+use {Scalar, Endianness};
 
 /// Trait which defines the minimum requirements for a pixel layout implementation.
 ///
@@ -34,14 +34,74 @@ pub trait Pixel: Copy + Clone + Debug + PartialEq<Self> {
     /// there are cases where the result is `None`. A common case for this to happen
     /// is when the given `pitch` is smaller than `calc_minimum_pitch(width, height)`.
     fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize>;
+    /// Size of a single scalar in bytes.
+    ///
+    /// This is the unit whose byte order is swapped by the default implementations of the
+    /// `*_with_endianness` methods. The default of `1` means that the byte order does not
+    /// matter.
+    ///
+    /// # Correctness
+    /// Pixel types with multi-byte scalars which rely on the default `*_with_endianness`
+    /// implementations have to override this, otherwise no bytes are swapped.
+    const SCALAR_SIZE: usize = 1;
     /// Loads a `Pixel` out of a raw buffer.
     ///
-    /// This is important for input output functionality.
+    /// This is important for input output functionality. The buffer is expected in the byte
+    /// order of the host.
     fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self;
     /// Writes a `Pixel` into a raw buffer.
     ///
-    /// This is important for input output functionality.
+    /// This is important for input output functionality. The buffer is written in the byte
+    /// order of the host.
     fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]);
+    /// Loads a `Pixel` out of a raw buffer whose scalars are stored in the given byte order.
+    ///
+    /// The default implementation delegates to
+    /// [`load_from_raw_buffer`](#tymethod.load_from_raw_buffer) and reverses every scalar of
+    /// [`SCALAR_SIZE`](#associatedconstant.SCALAR_SIZE) bytes first if the byte order differs
+    /// from the host. It assumes that pixel `x` occupies the `calc_minimum_pitch(1, 1)` bytes
+    /// starting at offset `x * calc_minimum_pitch(1, 1)` of its row.
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            endianness: Endianness)
+                                            -> Self {
+        if endianness.is_native() {
+            return Self::load_from_raw_buffer(x, y, pitch, buffer);
+        }
+        let size = Self::calc_minimum_pitch(1, 1);
+        let start = (y as usize) * (pitch as usize) + (x as usize) * size;
+        let mut bytes = buffer[start..start + size].to_vec();
+        for scalar in bytes.chunks_mut(Self::SCALAR_SIZE) {
+            scalar.reverse();
+        }
+        Self::load_from_raw_buffer(0, 0, size as u32, &bytes)
+    }
+    /// Writes a `Pixel` into a raw buffer, storing its scalars in the given byte order.
+    ///
+    /// The default implementation delegates to
+    /// [`write_into_raw_buffer`](#tymethod.write_into_raw_buffer), see
+    /// [`load_from_raw_buffer_with_endianness`](#method.load_from_raw_buffer_with_endianness)
+    /// for the assumptions it makes.
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             endianness: Endianness) {
+        if endianness.is_native() {
+            return self.write_into_raw_buffer(x, y, pitch, buffer);
+        }
+        let size = Self::calc_minimum_pitch(1, 1);
+        let start = (y as usize) * (pitch as usize) + (x as usize) * size;
+        let mut bytes = vec![0u8; size];
+        self.write_into_raw_buffer(0, 0, size as u32, &mut bytes);
+        for scalar in bytes.chunks_mut(Self::SCALAR_SIZE) {
+            scalar.reverse();
+        }
+        buffer[start..start + size].copy_from_slice(&bytes);
+    }
 }
 
 /// Trait for [`Pixel`](trait.Pixel.html) types which can be used for arithmetic operations.
@@ -81,6 +141,22 @@ pub trait PixelArithmetic: Pixel {
     /// ```
     type ScalarT: Scalar;
 
+    /// Number of channels of this [`Pixel`](trait.Pixel.html) type.
+    const CHANNEL_COUNT: usize;
+    /// Index of the channel which holds the (linear) alpha value, if there is one.
+    const ALPHA_CHANNEL: Option<usize> = None;
+
+    /// Returns the value of channel `idx`.
+    ///
+    /// # Panics
+    /// If `idx` is not smaller than `CHANNEL_COUNT`.
+    fn get_channel(&self, idx: usize) -> Self::ScalarT;
+    /// Sets the value of channel `idx`.
+    ///
+    /// # Panics
+    /// If `idx` is not smaller than `CHANNEL_COUNT`.
+    fn set_channel(&mut self, idx: usize, value: Self::ScalarT);
+
     // pixel <op> pixel
 
     /// Add two pixels.
@@ -112,3 +188,39 @@ pub trait PixelArithmetic: Pixel {
     /// Divide a scalar and a pixel
     fn div_sc_px(self, lhs: Self::ScalarT) -> Self;
 }
+
+/// Trait for [`PixelArithmetic`](trait.PixelArithmetic.html) types with checked arithmetic.
+///
+/// In contrast to the operations of [`PixelArithmetic`](trait.PixelArithmetic.html) the result
+/// is `None` if the operation overflows for any channel or if any channel is divided by zero,
+/// see [`Scalar::checked_add`](trait.Scalar.html#tymethod.checked_add). This is implemented for
+/// every [`PixelArithmetic`](trait.PixelArithmetic.html) type by applying the operation
+/// channel-wise.
+///
+/// # Examples
+/// ```
+/// use img::{ScalarVal, GrayVal8U};
+/// let a = GrayVal8U::new(ScalarVal(200));
+/// let b = GrayVal8U::new(ScalarVal(100));
+/// assert_eq!(a.checked_add(b), None);
+/// assert_eq!(a.checked_sub(b), Some(GrayVal8U::new(ScalarVal(100))));
+/// ```
+pub trait PixelCheckedArithmetic: PixelArithmetic {
+    /// Add two pixels.
+    fn checked_add_px_px(self, rhs: Self) -> Option<Self>;
+    /// Subtract two pixels.
+    fn checked_sub_px_px(self, rhs: Self) -> Option<Self>;
+    /// Multiply two pixels.
+    fn checked_mul_px_px(self, rhs: Self) -> Option<Self>;
+    /// Divide two pixels.
+    fn checked_div_px_px(self, rhs: Self) -> Option<Self>;
+
+    /// Add a pixel and a scalar
+    fn checked_add_px_sc(self, rhs: Self::ScalarT) -> Option<Self>;
+    /// Subtract a pixel and a scalar
+    fn checked_sub_px_sc(self, rhs: Self::ScalarT) -> Option<Self>;
+    /// Multiply a pixel and a scalar
+    fn checked_mul_px_sc(self, rhs: Self::ScalarT) -> Option<Self>;
+    /// Divide a pixel and a scalar
+    fn checked_div_px_sc(self, rhs: Self::ScalarT) -> Option<Self>;
+}
@@ -0,0 +1,72 @@
+use {Pixel, PixelVal, Endianness};
+
+/// Defines a binary pixel type, e.g. for masks and the result of thresholding.
+///
+/// Every pixel is stored in one byte, which is `1` for set and `0` for unset pixels. When
+/// loading from a raw buffer every non-zero byte is treated as set.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Binary {
+    value: bool,
+}
+
+impl Pixel for Binary {
+    fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+        width as usize
+    }
+
+    fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+        if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+            Some((height as usize) * (pitch as usize))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            _endianness: Endianness)
+                                            -> Self {
+        let idx = (y as usize) * (pitch as usize) + x as usize;
+        Binary { value: buffer[idx] != 0 }
+    }
+
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             _endianness: Endianness) {
+        let idx = (y as usize) * (pitch as usize) + x as usize;
+        buffer[idx] = self.value as u8;
+    }
+}
+
+/// Convenient abbreviation for [`Binary`](struct.Binary.html) [`PixelVal`s](struct.PixelVal.html)
+pub type BinaryVal = PixelVal<Binary>;
+
+impl BinaryVal {
+    /// Constructs a `BinaryVal` based on a given value.
+    pub fn new(value: bool) -> BinaryVal {
+        PixelVal(Binary { value })
+    }
+
+    /// Getter for the value
+    pub fn value(&self) -> bool {
+        self.0.value
+    }
+
+    /// Setter for the value
+    pub fn set_value(&mut self, value: bool) {
+        self.0.value = value;
+    }
+}
@@ -0,0 +1,182 @@
+use std::mem::size_of;
+use {Scalar, ScalarVal, Pixel, Endianness, PixelArithmetic, PixelVal};
+
+/// Defines a pixel type with `N` channels of the same scalar type.
+///
+/// This is the generic counterpart of the named pixel types like [`Gray`](struct.Gray.html) or
+/// [`Rgb`](struct.Rgb.html), e.g. for multispectral data. The channels have no further meaning,
+/// in a raw buffer they are stored interleaved in the order of their index and all arithmetic
+/// is element-wise.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Channels<BaseTypeP, const N: usize>
+    where BaseTypeP: Scalar
+{
+    values: [BaseTypeP; N],
+}
+
+impl<BaseTypeP, const N: usize> Pixel for Channels<BaseTypeP, N>
+    where BaseTypeP: Scalar
+{
+    const SCALAR_SIZE: usize = size_of::<BaseTypeP>();
+
+    fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+        (width as usize) * N * size_of::<BaseTypeP>()
+    }
+
+    fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+        if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+            Some((height as usize) * (pitch as usize))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            endianness: Endianness)
+                                            -> Self {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * N * size;
+        let end = start + N * size;
+        assert!(end <= buffer.len());
+        let mut values = [BaseTypeP::range_min(); N];
+        for (idx, value) in values.iter_mut().enumerate() {
+            let offset = start + idx * size;
+            *value = BaseTypeP::from_bytes(&buffer[offset..offset + size], endianness);
+        }
+        Channels { values }
+    }
+
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             endianness: Endianness) {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * N * size;
+        let end = start + N * size;
+        assert!(end <= buffer.len());
+        for (idx, value) in self.values.iter().enumerate() {
+            let offset = start + idx * size;
+            value.write_bytes(&mut buffer[offset..offset + size], endianness);
+        }
+    }
+}
+
+impl<BaseTypeP, const N: usize> Channels<BaseTypeP, N>
+    where BaseTypeP: Scalar
+{
+    fn map<F>(mut self, f: F) -> Self
+        where F: Fn(BaseTypeP) -> BaseTypeP
+    {
+        for value in self.values.iter_mut() {
+            *value = f(*value);
+        }
+        self
+    }
+
+    fn zip<F>(mut self, other: Self, f: F) -> Self
+        where F: Fn(BaseTypeP, BaseTypeP) -> BaseTypeP
+    {
+        for (value, &rhs) in self.values.iter_mut().zip(other.values.iter()) {
+            *value = f(*value, rhs);
+        }
+        self
+    }
+}
+
+impl<BaseTypeP, const N: usize> PixelArithmetic for Channels<BaseTypeP, N>
+    where BaseTypeP: Scalar
+{
+    type ScalarT = BaseTypeP;
+
+    const CHANNEL_COUNT: usize = N;
+
+    fn get_channel(&self, idx: usize) -> Self::ScalarT {
+        assert!(idx < N, "Invalid channel index ({:}) for {:} channels", idx, N);
+        self.values[idx]
+    }
+    fn set_channel(&mut self, idx: usize, value: Self::ScalarT) {
+        assert!(idx < N, "Invalid channel index ({:}) for {:} channels", idx, N);
+        self.values[idx] = value;
+    }
+
+    fn add_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a + b)
+    }
+    fn sub_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a - b)
+    }
+    fn mul_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a * b)
+    }
+    fn div_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a / b)
+    }
+
+    fn add_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a + rhs)
+    }
+    fn sub_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a - rhs)
+    }
+    fn mul_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a * rhs)
+    }
+    fn div_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a / rhs)
+    }
+
+    fn add_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs + a)
+    }
+    fn sub_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs - a)
+    }
+    fn mul_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs * a)
+    }
+    fn div_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs / a)
+    }
+}
+
+/// Convenient abbreviation for [`Channels`](struct.Channels.html)
+/// [`PixelVal`s](struct.PixelVal.html)
+pub type ChannelsVal<BaseTypeP, const N: usize> = PixelVal<Channels<BaseTypeP, N>>;
+
+impl<BaseTypeP, const N: usize> ChannelsVal<BaseTypeP, N>
+    where BaseTypeP: Scalar
+{
+    /// Constructs a `ChannelsVal` based on the given channel values.
+    pub fn new(values: [ScalarVal<BaseTypeP>; N]) -> ChannelsVal<BaseTypeP, N> {
+        PixelVal(Channels { values: values.map(|value| value.0) })
+    }
+
+    /// Getter for channel `idx`
+    ///
+    /// # Panics
+    /// If `idx` is not smaller than `N`.
+    pub fn channel(&self, idx: usize) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.get_channel(idx))
+    }
+
+    /// Setter for channel `idx`
+    ///
+    /// # Panics
+    /// If `idx` is not smaller than `N`.
+    pub fn set_channel(&mut self, idx: usize, value: ScalarVal<BaseTypeP>) {
+        self.0.set_channel(idx, value.0);
+    }
+}
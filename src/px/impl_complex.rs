@@ -0,0 +1,113 @@
+use std::mem::size_of;
+use {Scalar, ScalarVal, Pixel, Endianness, PixelVal};
+
+/// Defines a complex valued pixel type, e.g. for the result of a Fourier transform.
+///
+/// In a raw buffer the real part is followed by the imaginary part. Complex multiplication
+/// and division do not work channel-wise, therefore this type does not implement
+/// [`PixelArithmetic`](trait.PixelArithmetic.html).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Complex<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    re: BaseTypeP,
+    im: BaseTypeP,
+}
+
+impl<BaseTypeP> Pixel for Complex<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    const SCALAR_SIZE: usize = size_of::<BaseTypeP>();
+
+    fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+        (width as usize) * 2 * size_of::<BaseTypeP>()
+    }
+
+    fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+        if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+            Some((height as usize) * (pitch as usize))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            endianness: Endianness)
+                                            -> Self {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * 2 * size;
+        let end = start + 2 * size;
+        assert!(end <= buffer.len());
+        let part = |idx: usize| {
+            let offset = start + idx * size;
+            BaseTypeP::from_bytes(&buffer[offset..offset + size], endianness)
+        };
+        Complex {
+            re: part(0),
+            im: part(1),
+        }
+    }
+
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             endianness: Endianness) {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * 2 * size;
+        let end = start + 2 * size;
+        assert!(end <= buffer.len());
+        for (idx, value) in [self.re, self.im].iter().enumerate() {
+            let offset = start + idx * size;
+            value.write_bytes(&mut buffer[offset..offset + size], endianness);
+        }
+    }
+}
+
+/// Convenient abbreviation for [`Complex`](struct.Complex.html)
+/// [`PixelVal`s](struct.PixelVal.html)
+pub type ComplexVal<BaseTypeP> = PixelVal<Complex<BaseTypeP>>;
+
+impl<BaseTypeP> ComplexVal<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    /// Constructs a `ComplexVal` based on its real and imaginary part.
+    pub fn new(re: ScalarVal<BaseTypeP>, im: ScalarVal<BaseTypeP>) -> ComplexVal<BaseTypeP> {
+        PixelVal(Complex {
+            re: re.0,
+            im: im.0,
+        })
+    }
+
+    /// Getter for the real part
+    pub fn re(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.re)
+    }
+
+    /// Getter for the imaginary part
+    pub fn im(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.im)
+    }
+}
+
+/// Convenient abbreviation
+pub type Complex32F = Complex<f32>;
+/// Convenient abbreviation
+pub type Complex64F = Complex<f64>;
+
+/// Convenient abbreviation
+pub type ComplexVal32F = ComplexVal<f32>;
+/// Convenient abbreviation
+pub type ComplexVal64F = ComplexVal<f64>;
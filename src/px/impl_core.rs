@@ -2,7 +2,7 @@ use std::ops::{Add, AddAssign};
 use std::ops::{Sub, SubAssign};
 use std::ops::{Mul, MulAssign};
 use std::ops::{Div, DivAssign};
-use {ScalarVal, Pixel, PixelArithmetic};
+use {Scalar, ScalarVal, Pixel, PixelArithmetic, PixelCheckedArithmetic, Endianness};
 
 /// Newtype which wraps [`Pixel`](trait.Pixel.html)
 ///
@@ -47,8 +47,102 @@ impl<PixelP> PixelVal<PixelP>
     pub fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
         self.0.write_into_raw_buffer(x, y, pitch, buffer)
     }
+    #[allow(missing_docs)]
+    pub fn load_from_raw_buffer_with_endianness(x: u32,
+                                                y: u32,
+                                                pitch: u32,
+                                                buffer: &[u8],
+                                                endianness: Endianness)
+                                                -> Self {
+        PixelVal(PixelP::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, endianness))
+    }
+    #[allow(missing_docs)]
+    pub fn write_into_raw_buffer_with_endianness(&self,
+                                                 x: u32,
+                                                 y: u32,
+                                                 pitch: u32,
+                                                 buffer: &mut [u8],
+                                                 endianness: Endianness) {
+        self.0.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, endianness)
+    }
+}
+
+/// Checked variants of the arithmetic operators, see
+/// [`PixelCheckedArithmetic`](trait.PixelCheckedArithmetic.html).
+impl<PixelP> PixelVal<PixelP>
+    where PixelP: PixelArithmetic
+{
+    /// Adds two pixels, returns `None` if any channel overflows.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add_px_px(rhs.0).map(PixelVal)
+    }
+    /// Subtracts two pixels, returns `None` if any channel overflows.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub_px_px(rhs.0).map(PixelVal)
+    }
+    /// Multiplies two pixels, returns `None` if any channel overflows.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul_px_px(rhs.0).map(PixelVal)
+    }
+    /// Divides two pixels, returns `None` if any channel of `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.0.checked_div_px_px(rhs.0).map(PixelVal)
+    }
+}
+
+/// Combines every channel of `lhs` with the channel at the same index of `rhs`.
+fn checked_zip<PixelP, F>(mut lhs: PixelP, rhs: &PixelP, f: F) -> Option<PixelP>
+    where PixelP: PixelArithmetic,
+          F: Fn(PixelP::ScalarT, PixelP::ScalarT) -> Option<PixelP::ScalarT>
+{
+    for idx in 0..PixelP::CHANNEL_COUNT {
+        let value = f(lhs.get_channel(idx), rhs.get_channel(idx))?;
+        lhs.set_channel(idx, value);
+    }
+    Some(lhs)
 }
 
+/// Replaces every channel of `pixel` by the result of `f`.
+fn checked_map<PixelP, F>(mut pixel: PixelP, f: F) -> Option<PixelP>
+    where PixelP: PixelArithmetic,
+          F: Fn(PixelP::ScalarT) -> Option<PixelP::ScalarT>
+{
+    for idx in 0..PixelP::CHANNEL_COUNT {
+        let value = f(pixel.get_channel(idx))?;
+        pixel.set_channel(idx, value);
+    }
+    Some(pixel)
+}
+
+impl<PixelP> PixelCheckedArithmetic for PixelP
+    where PixelP: PixelArithmetic
+{
+    fn checked_add_px_px(self, rhs: Self) -> Option<Self> {
+        checked_zip(self, &rhs, Scalar::checked_add)
+    }
+    fn checked_sub_px_px(self, rhs: Self) -> Option<Self> {
+        checked_zip(self, &rhs, Scalar::checked_sub)
+    }
+    fn checked_mul_px_px(self, rhs: Self) -> Option<Self> {
+        checked_zip(self, &rhs, Scalar::checked_mul)
+    }
+    fn checked_div_px_px(self, rhs: Self) -> Option<Self> {
+        checked_zip(self, &rhs, Scalar::checked_div)
+    }
+
+    fn checked_add_px_sc(self, rhs: Self::ScalarT) -> Option<Self> {
+        checked_map(self, |a| a.checked_add(rhs))
+    }
+    fn checked_sub_px_sc(self, rhs: Self::ScalarT) -> Option<Self> {
+        checked_map(self, |a| a.checked_sub(rhs))
+    }
+    fn checked_mul_px_sc(self, rhs: Self::ScalarT) -> Option<Self> {
+        checked_map(self, |a| a.checked_mul(rhs))
+    }
+    fn checked_div_px_sc(self, rhs: Self::ScalarT) -> Option<Self> {
+        checked_map(self, |a| a.checked_div(rhs))
+    }
+}
 
 // implement all std ops through PixelArithmetic trait
 
@@ -1,5 +1,5 @@
-use std::mem::{size_of, transmute, transmute_copy};
-use {Scalar, ScalarVal, Pixel, PixelArithmetic, PixelVal};
+use std::mem::size_of;
+use {Scalar, ScalarVal, Pixel, Endianness, PixelArithmetic, PixelVal};
 
 /// Defines a simple grayscale pixel type.
 ///
@@ -17,6 +17,8 @@ pub struct Gray<BaseTypeP>
 impl<BaseTypeP> Pixel for Gray<BaseTypeP>
     where BaseTypeP: Scalar
 {
+    const SCALAR_SIZE: usize = size_of::<BaseTypeP>();
+
     fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
         (width as usize) * size_of::<BaseTypeP>()
     }
@@ -30,19 +32,35 @@ impl<BaseTypeP> Pixel for Gray<BaseTypeP>
     }
 
     fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
-        let start = (y * pitch) as usize + x as usize * size_of::<BaseTypeP>();
-        let end = start + size_of::<BaseTypeP>();
-        assert!(end <= buffer.len());
-        Gray { intensity: unsafe { transmute_copy(&buffer[start]) } }
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
     }
 
     fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
-        let start = (y * pitch) as usize + x as usize * size_of::<BaseTypeP>();
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            endianness: Endianness)
+                                            -> Self {
+        let start = (y as usize) * (pitch as usize) + (x as usize) * size_of::<BaseTypeP>();
         let end = start + size_of::<BaseTypeP>();
+        assert!(end <= buffer.len());
+        Gray { intensity: BaseTypeP::from_bytes(&buffer[start..end], endianness) }
+    }
 
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             endianness: Endianness) {
+        let start = (y as usize) * (pitch as usize) + (x as usize) * size_of::<BaseTypeP>();
+        let end = start + size_of::<BaseTypeP>();
         assert!(end <= buffer.len());
-        let intensity: &mut BaseTypeP = unsafe { transmute(&mut buffer[start]) };
-        *intensity = self.intensity;
+        self.intensity.write_bytes(&mut buffer[start..end], endianness);
     }
 }
 
@@ -51,6 +69,17 @@ impl<BaseTypeP> PixelArithmetic for Gray<BaseTypeP>
 {
     type ScalarT = BaseTypeP;
 
+    const CHANNEL_COUNT: usize = 1;
+
+    fn get_channel(&self, idx: usize) -> Self::ScalarT {
+        assert_eq!(idx, 0);
+        self.intensity
+    }
+    fn set_channel(&mut self, idx: usize, value: Self::ScalarT) {
+        assert_eq!(idx, 0);
+        self.intensity = value;
+    }
+
     fn add_px_px(self, rhs: Self) -> Self {
         Gray { intensity: self.intensity + rhs.intensity }
     }
@@ -120,6 +149,16 @@ pub type Gray16U = Gray<u16>;
 /// Convenient abbreviation
 pub type Gray32U = Gray<u32>;
 /// Convenient abbreviation
+pub type Gray64U = Gray<u64>;
+/// Convenient abbreviation
+pub type Gray8I = Gray<i8>;
+/// Convenient abbreviation
+pub type Gray16I = Gray<i16>;
+/// Convenient abbreviation
+pub type Gray32I = Gray<i32>;
+/// Convenient abbreviation
+pub type Gray64I = Gray<i64>;
+/// Convenient abbreviation
 pub type Gray32F = Gray<f32>;
 /// Convenient abbreviation
 pub type Gray64F = Gray<f64>;
@@ -131,6 +170,16 @@ pub type GrayVal16U = GrayVal<u16>;
 /// Convenient abbreviation
 pub type GrayVal32U = GrayVal<u32>;
 /// Convenient abbreviation
+pub type GrayVal64U = GrayVal<u64>;
+/// Convenient abbreviation
+pub type GrayVal8I = GrayVal<i8>;
+/// Convenient abbreviation
+pub type GrayVal16I = GrayVal<i16>;
+/// Convenient abbreviation
+pub type GrayVal32I = GrayVal<i32>;
+/// Convenient abbreviation
+pub type GrayVal64I = GrayVal<i64>;
+/// Convenient abbreviation
 pub type GrayVal32F = GrayVal<f32>;
 /// Convenient abbreviation
 pub type GrayVal64F = GrayVal<f64>;
\ No newline at end of file
@@ -0,0 +1,73 @@
+use {Pixel, PixelVal, Endianness};
+
+/// Defines an indexed pixel type, whose color is the entry `index` of an external palette.
+///
+/// Every pixel is stored in one byte, so a palette can have up to 256 entries. As the color
+/// is only known together with the palette, there is no arithmetic on indexed pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    index: u8,
+}
+
+impl Pixel for Palette {
+    fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+        width as usize
+    }
+
+    fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+        if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+            Some((height as usize) * (pitch as usize))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            _endianness: Endianness)
+                                            -> Self {
+        let idx = (y as usize) * (pitch as usize) + x as usize;
+        Palette { index: buffer[idx] }
+    }
+
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             _endianness: Endianness) {
+        let idx = (y as usize) * (pitch as usize) + x as usize;
+        buffer[idx] = self.index;
+    }
+}
+
+/// Convenient abbreviation for [`Palette`](struct.Palette.html)
+/// [`PixelVal`s](struct.PixelVal.html)
+pub type PaletteVal = PixelVal<Palette>;
+
+impl PaletteVal {
+    /// Constructs a `PaletteVal` based on a given palette index.
+    pub fn new(index: u8) -> PaletteVal {
+        PixelVal(Palette { index })
+    }
+
+    /// Getter for the palette index
+    pub fn index(&self) -> u8 {
+        self.0.index
+    }
+
+    /// Setter for the palette index
+    pub fn set_index(&mut self, index: u8) {
+        self.0.index = index;
+    }
+}
@@ -0,0 +1,230 @@
+use std::mem::size_of;
+use {Scalar, ScalarVal, Pixel, Endianness, PixelArithmetic, PixelVal};
+
+/// Defines a color pixel type with red, green and blue channels.
+///
+/// The `BaseTypeP` type parameter specifies the data type used to store every channel, see
+/// [`Gray`](struct.Gray.html). In a raw buffer the channels are stored interleaved in the
+/// order red, green, blue.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rgb<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    r: BaseTypeP,
+    g: BaseTypeP,
+    b: BaseTypeP,
+}
+
+impl<BaseTypeP> Pixel for Rgb<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    const SCALAR_SIZE: usize = size_of::<BaseTypeP>();
+
+    fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+        (width as usize) * 3 * size_of::<BaseTypeP>()
+    }
+
+    fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+        if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+            Some((height as usize) * (pitch as usize))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            endianness: Endianness)
+                                            -> Self {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * 3 * size;
+        let end = start + 3 * size;
+        assert!(end <= buffer.len());
+        let channel = |idx: usize| {
+            let offset = start + idx * size;
+            BaseTypeP::from_bytes(&buffer[offset..offset + size], endianness)
+        };
+        Rgb {
+            r: channel(0),
+            g: channel(1),
+            b: channel(2),
+        }
+    }
+
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             endianness: Endianness) {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * 3 * size;
+        let end = start + 3 * size;
+        assert!(end <= buffer.len());
+        for (idx, value) in [self.r, self.g, self.b].iter().enumerate() {
+            let offset = start + idx * size;
+            value.write_bytes(&mut buffer[offset..offset + size], endianness);
+        }
+    }
+}
+
+impl<BaseTypeP> Rgb<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    fn map<F>(self, f: F) -> Self
+        where F: Fn(BaseTypeP) -> BaseTypeP
+    {
+        Rgb {
+            r: f(self.r),
+            g: f(self.g),
+            b: f(self.b),
+        }
+    }
+
+    fn zip<F>(self, other: Self, f: F) -> Self
+        where F: Fn(BaseTypeP, BaseTypeP) -> BaseTypeP
+    {
+        Rgb {
+            r: f(self.r, other.r),
+            g: f(self.g, other.g),
+            b: f(self.b, other.b),
+        }
+    }
+}
+
+impl<BaseTypeP> PixelArithmetic for Rgb<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    type ScalarT = BaseTypeP;
+
+    const CHANNEL_COUNT: usize = 3;
+
+    fn get_channel(&self, idx: usize) -> Self::ScalarT {
+        match idx {
+            0 => self.r,
+            1 => self.g,
+            2 => self.b,
+            _ => panic!("Invalid channel index ({:}) for Rgb", idx),
+        }
+    }
+    fn set_channel(&mut self, idx: usize, value: Self::ScalarT) {
+        match idx {
+            0 => self.r = value,
+            1 => self.g = value,
+            2 => self.b = value,
+            _ => panic!("Invalid channel index ({:}) for Rgb", idx),
+        }
+    }
+
+    fn add_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a + b)
+    }
+    fn sub_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a - b)
+    }
+    fn mul_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a * b)
+    }
+    fn div_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a / b)
+    }
+
+    fn add_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a + rhs)
+    }
+    fn sub_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a - rhs)
+    }
+    fn mul_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a * rhs)
+    }
+    fn div_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a / rhs)
+    }
+
+    fn add_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs + a)
+    }
+    fn sub_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs - a)
+    }
+    fn mul_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs * a)
+    }
+    fn div_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs / a)
+    }
+}
+
+/// Convenient abbreviation for [`Rgb`](struct.Rgb.html) [`PixelVal`s](struct.PixelVal.html)
+pub type RgbVal<BaseTypeP> = PixelVal<Rgb<BaseTypeP>>;
+
+impl<BaseTypeP> RgbVal<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    /// Constructs a `RgbVal` based on the given channel values.
+    pub fn new(r: ScalarVal<BaseTypeP>,
+               g: ScalarVal<BaseTypeP>,
+               b: ScalarVal<BaseTypeP>)
+               -> RgbVal<BaseTypeP> {
+        PixelVal(Rgb {
+            r: r.0,
+            g: g.0,
+            b: b.0,
+        })
+    }
+
+    /// Getter for the red channel
+    pub fn r(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.r)
+    }
+
+    /// Getter for the green channel
+    pub fn g(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.g)
+    }
+
+    /// Getter for the blue channel
+    pub fn b(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.b)
+    }
+
+    /// Setter for the red channel
+    pub fn set_r(&mut self, r: ScalarVal<BaseTypeP>) {
+        self.0.r = r.0;
+    }
+
+    /// Setter for the green channel
+    pub fn set_g(&mut self, g: ScalarVal<BaseTypeP>) {
+        self.0.g = g.0;
+    }
+
+    /// Setter for the blue channel
+    pub fn set_b(&mut self, b: ScalarVal<BaseTypeP>) {
+        self.0.b = b.0;
+    }
+}
+
+/// Convenient abbreviation
+pub type Rgb8U = Rgb<u8>;
+/// Convenient abbreviation
+pub type Rgb16U = Rgb<u16>;
+/// Convenient abbreviation
+pub type Rgb32F = Rgb<f32>;
+
+/// Convenient abbreviation
+pub type RgbVal8U = RgbVal<u8>;
+/// Convenient abbreviation
+pub type RgbVal16U = RgbVal<u16>;
+/// Convenient abbreviation
+pub type RgbVal32F = RgbVal<f32>;
@@ -0,0 +1,258 @@
+use std::mem::size_of;
+use {Scalar, ScalarVal, Pixel, Endianness, PixelArithmetic, PixelVal};
+
+/// Defines a color pixel type with red, green, blue and alpha channels.
+///
+/// The `BaseTypeP` type parameter specifies the data type used to store every channel, see
+/// [`Gray`](struct.Gray.html). In a raw buffer the channels are stored interleaved in the
+/// order red, green, blue, alpha. The color channels are not premultiplied, the alpha channel
+/// spans the [scalar range](trait.Scalar.html#tymethod.range_min) from transparent to opaque.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rgba<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    r: BaseTypeP,
+    g: BaseTypeP,
+    b: BaseTypeP,
+    a: BaseTypeP,
+}
+
+impl<BaseTypeP> Pixel for Rgba<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    const SCALAR_SIZE: usize = size_of::<BaseTypeP>();
+
+    fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+        (width as usize) * 4 * size_of::<BaseTypeP>()
+    }
+
+    fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+        if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+            Some((height as usize) * (pitch as usize))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            endianness: Endianness)
+                                            -> Self {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * 4 * size;
+        let end = start + 4 * size;
+        assert!(end <= buffer.len());
+        let channel = |idx: usize| {
+            let offset = start + idx * size;
+            BaseTypeP::from_bytes(&buffer[offset..offset + size], endianness)
+        };
+        Rgba {
+            r: channel(0),
+            g: channel(1),
+            b: channel(2),
+            a: channel(3),
+        }
+    }
+
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             endianness: Endianness) {
+        let size = size_of::<BaseTypeP>();
+        let start = (y as usize) * (pitch as usize) + (x as usize) * 4 * size;
+        let end = start + 4 * size;
+        assert!(end <= buffer.len());
+        for (idx, value) in [self.r, self.g, self.b, self.a].iter().enumerate() {
+            let offset = start + idx * size;
+            value.write_bytes(&mut buffer[offset..offset + size], endianness);
+        }
+    }
+}
+
+impl<BaseTypeP> Rgba<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    fn map<F>(self, f: F) -> Self
+        where F: Fn(BaseTypeP) -> BaseTypeP
+    {
+        Rgba {
+            r: f(self.r),
+            g: f(self.g),
+            b: f(self.b),
+            a: f(self.a),
+        }
+    }
+
+    fn zip<F>(self, other: Self, f: F) -> Self
+        where F: Fn(BaseTypeP, BaseTypeP) -> BaseTypeP
+    {
+        Rgba {
+            r: f(self.r, other.r),
+            g: f(self.g, other.g),
+            b: f(self.b, other.b),
+            a: f(self.a, other.a),
+        }
+    }
+}
+
+impl<BaseTypeP> PixelArithmetic for Rgba<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    type ScalarT = BaseTypeP;
+
+    const CHANNEL_COUNT: usize = 4;
+    const ALPHA_CHANNEL: Option<usize> = Some(3);
+
+    fn get_channel(&self, idx: usize) -> Self::ScalarT {
+        match idx {
+            0 => self.r,
+            1 => self.g,
+            2 => self.b,
+            3 => self.a,
+            _ => panic!("Invalid channel index ({:}) for Rgba", idx),
+        }
+    }
+    fn set_channel(&mut self, idx: usize, value: Self::ScalarT) {
+        match idx {
+            0 => self.r = value,
+            1 => self.g = value,
+            2 => self.b = value,
+            3 => self.a = value,
+            _ => panic!("Invalid channel index ({:}) for Rgba", idx),
+        }
+    }
+
+    fn add_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a + b)
+    }
+    fn sub_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a - b)
+    }
+    fn mul_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a * b)
+    }
+    fn div_px_px(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a / b)
+    }
+
+    fn add_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a + rhs)
+    }
+    fn sub_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a - rhs)
+    }
+    fn mul_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a * rhs)
+    }
+    fn div_px_sc(self, rhs: Self::ScalarT) -> Self {
+        self.map(|a| a / rhs)
+    }
+
+    fn add_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs + a)
+    }
+    fn sub_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs - a)
+    }
+    fn mul_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs * a)
+    }
+    fn div_sc_px(self, lhs: Self::ScalarT) -> Self {
+        self.map(|a| lhs / a)
+    }
+}
+
+/// Convenient abbreviation for [`Rgba`](struct.Rgba.html) [`PixelVal`s](struct.PixelVal.html)
+pub type RgbaVal<BaseTypeP> = PixelVal<Rgba<BaseTypeP>>;
+
+impl<BaseTypeP> RgbaVal<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    /// Constructs a `RgbaVal` based on the given channel values.
+    pub fn new(r: ScalarVal<BaseTypeP>,
+               g: ScalarVal<BaseTypeP>,
+               b: ScalarVal<BaseTypeP>,
+               a: ScalarVal<BaseTypeP>)
+               -> RgbaVal<BaseTypeP> {
+        PixelVal(Rgba {
+            r: r.0,
+            g: g.0,
+            b: b.0,
+            a: a.0,
+        })
+    }
+
+    /// Getter for the red channel
+    pub fn r(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.r)
+    }
+
+    /// Getter for the green channel
+    pub fn g(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.g)
+    }
+
+    /// Getter for the blue channel
+    pub fn b(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.b)
+    }
+
+    /// Getter for the alpha channel
+    pub fn a(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.a)
+    }
+
+    /// Composites this pixel over `other` with the additional opacity `t`.
+    ///
+    /// This is the Porter-Duff "over" operator with the alpha value of this pixel, normalized
+    /// by the [scalar range](trait.Scalar.html#tymethod.range_min), multiplied by `t`. So with
+    /// `t = 0` the result is `other` and with `t = 1` an opaque pixel covers `other` completely.
+    /// The colors are not premultiplied, if the result is fully transparent all channels are
+    /// set to the lower end of the scalar range.
+    ///
+    /// # Panics
+    /// If `t` is outside of `[0, 1]`.
+    pub fn alpha_blend(self, other: Self, t: f64) -> Self {
+        assert!((0.0..=1.0).contains(&t), "Blend factor ({:}) has to be in [0, 1]", t);
+        let min = BaseTypeP::range_min().to_f64();
+        let max = BaseTypeP::range_max().to_f64();
+        let normalize = |value: BaseTypeP| (value.to_f64() - min) / (max - min);
+
+        let src_alpha = normalize(self.0.a) * t;
+        let dst_alpha = normalize(other.0.a) * (1.0 - src_alpha);
+        let alpha = src_alpha + dst_alpha;
+        let blend = |src: BaseTypeP, dst: BaseTypeP| if alpha > 0.0 {
+            Scalar::from_f64((src.to_f64() * src_alpha + dst.to_f64() * dst_alpha) / alpha)
+        } else {
+            BaseTypeP::range_min()
+        };
+        PixelVal(Rgba {
+            r: blend(self.0.r, other.0.r),
+            g: blend(self.0.g, other.0.g),
+            b: blend(self.0.b, other.0.b),
+            a: Scalar::from_f64(min + alpha * (max - min)),
+        })
+    }
+}
+
+/// Convenient abbreviation
+pub type Rgba8U = Rgba<u8>;
+/// Convenient abbreviation
+pub type Rgba16U = Rgba<u16>;
+
+/// Convenient abbreviation
+pub type RgbaVal8U = RgbaVal<u8>;
+/// Convenient abbreviation
+pub type RgbaVal16U = RgbaVal<u16>;
@@ -0,0 +1,158 @@
+use std::mem::size_of;
+use {Scalar, ScalarVal, Pixel, Endianness, PixelArithmetic, PixelVal};
+
+/// Grayscale pixel type whose arithmetic saturates instead of overflowing.
+///
+/// The memory layout is the same as for [`Gray`](struct.Gray.html), only the arithmetic
+/// differs: for integer types additions and multiplications stop at the largest and
+/// subtractions at the smallest representable value, so `250 + 10` is `255` for `u8`.
+/// Floating point types behave like [`Gray`](struct.Gray.html).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SaturatingGray<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    intensity: BaseTypeP,
+}
+
+impl<BaseTypeP> Pixel for SaturatingGray<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    const SCALAR_SIZE: usize = size_of::<BaseTypeP>();
+
+    fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+        (width as usize) * size_of::<BaseTypeP>()
+    }
+
+    fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+        if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+            Some((height as usize) * (pitch as usize))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+        Self::load_from_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+        self.write_into_raw_buffer_with_endianness(x, y, pitch, buffer, Endianness::Native)
+    }
+
+    fn load_from_raw_buffer_with_endianness(x: u32,
+                                            y: u32,
+                                            pitch: u32,
+                                            buffer: &[u8],
+                                            endianness: Endianness)
+                                            -> Self {
+        let start = (y as usize) * (pitch as usize) + (x as usize) * size_of::<BaseTypeP>();
+        let end = start + size_of::<BaseTypeP>();
+        assert!(end <= buffer.len());
+        SaturatingGray { intensity: BaseTypeP::from_bytes(&buffer[start..end], endianness) }
+    }
+
+    fn write_into_raw_buffer_with_endianness(&self,
+                                             x: u32,
+                                             y: u32,
+                                             pitch: u32,
+                                             buffer: &mut [u8],
+                                             endianness: Endianness) {
+        let start = (y as usize) * (pitch as usize) + (x as usize) * size_of::<BaseTypeP>();
+        let end = start + size_of::<BaseTypeP>();
+        assert!(end <= buffer.len());
+        self.intensity.write_bytes(&mut buffer[start..end], endianness);
+    }
+}
+
+impl<BaseTypeP> PixelArithmetic for SaturatingGray<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    type ScalarT = BaseTypeP;
+
+    const CHANNEL_COUNT: usize = 1;
+
+    fn get_channel(&self, idx: usize) -> Self::ScalarT {
+        assert_eq!(idx, 0);
+        self.intensity
+    }
+    fn set_channel(&mut self, idx: usize, value: Self::ScalarT) {
+        assert_eq!(idx, 0);
+        self.intensity = value;
+    }
+
+    fn add_px_px(self, rhs: Self) -> Self {
+        SaturatingGray { intensity: self.intensity.saturating_add(rhs.intensity) }
+    }
+    fn sub_px_px(self, rhs: Self) -> Self {
+        SaturatingGray { intensity: self.intensity.saturating_sub(rhs.intensity) }
+    }
+    fn mul_px_px(self, rhs: Self) -> Self {
+        SaturatingGray { intensity: self.intensity.saturating_mul(rhs.intensity) }
+    }
+    fn div_px_px(self, rhs: Self) -> Self {
+        SaturatingGray { intensity: self.intensity / rhs.intensity }
+    }
+
+    fn add_px_sc(self, rhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: self.intensity.saturating_add(rhs) }
+    }
+    fn sub_px_sc(self, rhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: self.intensity.saturating_sub(rhs) }
+    }
+    fn mul_px_sc(self, rhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: self.intensity.saturating_mul(rhs) }
+    }
+    fn div_px_sc(self, rhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: self.intensity / rhs }
+    }
+
+    fn add_sc_px(self, lhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: lhs.saturating_add(self.intensity) }
+    }
+    fn sub_sc_px(self, lhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: lhs.saturating_sub(self.intensity) }
+    }
+    fn mul_sc_px(self, lhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: lhs.saturating_mul(self.intensity) }
+    }
+    fn div_sc_px(self, lhs: Self::ScalarT) -> Self {
+        SaturatingGray { intensity: lhs / self.intensity }
+    }
+}
+
+/// Convenient abbreviation for [`SaturatingGray`](struct.SaturatingGray.html)
+/// [`PixelVal`s](struct.PixelVal.html)
+pub type SaturatingGrayVal<BaseTypeP> = PixelVal<SaturatingGray<BaseTypeP>>;
+
+impl<BaseTypeP> SaturatingGrayVal<BaseTypeP>
+    where BaseTypeP: Scalar
+{
+    /// Constructs a `SaturatingGrayVal` based on a given intensity value.
+    pub fn new(intensity: ScalarVal<BaseTypeP>) -> SaturatingGrayVal<BaseTypeP> {
+        PixelVal(SaturatingGray { intensity: intensity.0 })
+    }
+
+    /// Getter for the intensity value
+    pub fn intensity(&self) -> ScalarVal<BaseTypeP> {
+        ScalarVal(self.0.intensity)
+    }
+
+    /// Setter for the intensity value
+    pub fn set_intensity(&mut self, intensity: ScalarVal<BaseTypeP>) {
+        self.0.intensity = intensity.0;
+    }
+}
+
+/// Convenient abbreviation
+pub type SaturatingGray8U = SaturatingGray<u8>;
+/// Convenient abbreviation
+pub type SaturatingGray16U = SaturatingGray<u16>;
+/// Convenient abbreviation
+pub type SaturatingGray32U = SaturatingGray<u32>;
+
+/// Convenient abbreviation
+pub type SaturatingGrayVal8U = SaturatingGrayVal<u8>;
+/// Convenient abbreviation
+pub type SaturatingGrayVal16U = SaturatingGrayVal<u16>;
+/// Convenient abbreviation
+pub type SaturatingGrayVal32U = SaturatingGrayVal<u32>;
@@ -1,10 +1,24 @@
 mod generic;
 mod impl_core;
 mod impl_gray;
+mod impl_binary;
+mod impl_rgb;
+mod impl_rgba;
+mod impl_complex;
+mod impl_palette;
+mod impl_saturating_gray;
+mod impl_channels;
 
 pub use self::generic::*;
 pub use self::impl_core::*;
 pub use self::impl_gray::*;
+pub use self::impl_binary::*;
+pub use self::impl_rgb::*;
+pub use self::impl_rgba::*;
+pub use self::impl_complex::*;
+pub use self::impl_palette::*;
+pub use self::impl_saturating_gray::*;
+pub use self::impl_channels::*;
 
 #[test]
 fn test_arithmetic() {
@@ -27,4 +41,290 @@ fn test_raw_buffer_funcs() {
     pixel += ScalarVal(1);
     pixel.write_into_raw_buffer(2, 0, 4, &mut buffer);
     assert_eq!(buffer, [0, 1, 2, 0]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_saturating_gray() {
+    use ScalarVal;
+
+    let white = SaturatingGrayVal8U::new(ScalarVal(255));
+    let black = SaturatingGrayVal8U::new(ScalarVal(0));
+    assert_eq!((white + ScalarVal(10)).intensity(), ScalarVal(255));
+    assert_eq!((black - ScalarVal(5)).intensity(), ScalarVal(0));
+    assert_eq!((ScalarVal(5) - white).intensity(), ScalarVal(0));
+    assert_eq!((white * white).intensity(), ScalarVal(255));
+    assert_eq!((SaturatingGrayVal8U::new(ScalarVal(200)) + SaturatingGrayVal8U::new(ScalarVal(100)))
+                   .intensity(),
+               ScalarVal(255));
+    assert_eq!((SaturatingGrayVal8U::new(ScalarVal(20)) - ScalarVal(5)).intensity(),
+               ScalarVal(15));
+    assert_eq!((SaturatingGrayVal16U::new(ScalarVal(60000)) + ScalarVal(6000)).intensity(),
+               ScalarVal(65535));
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    use ScalarVal;
+
+    let a = GrayVal8U::new(ScalarVal(200));
+    let b = GrayVal8U::new(ScalarVal(100));
+    let zero = GrayVal8U::new(ScalarVal(0));
+    assert_eq!(a.checked_add(b), None);
+    assert_eq!(b.checked_sub(a), None);
+    assert_eq!(a.checked_mul(b), None);
+    assert_eq!(a.checked_div(zero), None);
+    assert_eq!(a.0.checked_add_px_sc(56), None);
+    assert_eq!(a.0.checked_div_px_sc(0), None);
+
+    assert_eq!(b.checked_add(b), Some(GrayVal8U::new(ScalarVal(200))));
+    assert_eq!(a.checked_sub(b), Some(b));
+    assert_eq!(zero.checked_mul(a), Some(zero));
+    assert_eq!(a.checked_div(b), Some(GrayVal8U::new(ScalarVal(2))));
+    assert_eq!(a.0.checked_add_px_sc(55), Some(GrayVal8U::new(ScalarVal(255)).0));
+
+    let c = RgbVal8U::new(ScalarVal(10), ScalarVal(250), ScalarVal(0));
+    assert_eq!(c.checked_add(RgbVal8U::new(ScalarVal(10), ScalarVal(10), ScalarVal(0))), None);
+}
+
+#[test]
+fn test_gray_load_unaligned() {
+    use ScalarVal;
+
+    // a pitch of 5 puts the second row at an odd offset
+    let mut buffer = [0u8; 10];
+    buffer[5..7].copy_from_slice(&0x1234u16.to_ne_bytes());
+    buffer[7..9].copy_from_slice(&0xabcdu16.to_ne_bytes());
+    let first = GrayVal16U::load_from_raw_buffer(0, 1, 5, &buffer);
+    let second = GrayVal16U::load_from_raw_buffer(1, 1, 5, &buffer);
+    assert_eq!(first.intensity(), ScalarVal(0x1234));
+    assert_eq!(second.intensity(), ScalarVal(0xabcd));
+}
+
+#[test]
+fn test_signed_raw_buffer() {
+    use {ScalarVal, Endianness};
+
+    let mut buffer = [0u8; 9];
+    let pixel = GrayVal64I::new(ScalarVal(-2));
+    pixel.write_into_raw_buffer_with_endianness(0, 0, 8, &mut buffer[1..], Endianness::Big);
+    assert_eq!(buffer, [0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+    let loaded =
+        GrayVal64I::load_from_raw_buffer_with_endianness(0, 0, 8, &buffer[1..], Endianness::Big);
+    assert_eq!(loaded, pixel);
+    let small = GrayVal8I::new(ScalarVal(-128));
+    small.write_into_raw_buffer(0, 0, 1, &mut buffer);
+    assert_eq!(buffer[0], 0x80);
+    assert_eq!(GrayVal8I::load_from_raw_buffer(0, 0, 1, &buffer), small);
+}
+
+#[test]
+fn test_wide_accumulation() {
+    use {ScalarVal, Endianness};
+
+    let frame = GrayVal64U::new(ScalarVal(u32::MAX as u64));
+    let mut sum = GrayVal64U::new(ScalarVal(0));
+    for _ in 0..1000 {
+        sum += frame;
+    }
+    assert_eq!(sum.intensity(), ScalarVal(1000 * u32::MAX as u64));
+    assert_eq!(Gray64U::calc_minimum_pitch(3, 1), 24);
+
+    let mut buffer = [0u8; 16];
+    let wide = GrayVal::<i128>::new(ScalarVal(-(1i128 << 100)));
+    wide.write_into_raw_buffer_with_endianness(0, 0, 16, &mut buffer, Endianness::Little);
+    assert_eq!(buffer[12], 0xf0);
+    let loaded = GrayVal::<i128>::load_from_raw_buffer_with_endianness(0,
+                                                                         0,
+                                                                         16,
+                                                                         &buffer,
+                                                                         Endianness::Little);
+    assert_eq!(loaded, wide);
+}
+
+#[test]
+fn test_gray_write_unaligned() {
+    use ScalarVal;
+
+    // a pitch of 3 puts the second row at a byte offset of 3
+    let mut buffer = [0xffu8; 10];
+    let pixel = GrayVal32F::new(ScalarVal(0.3));
+    pixel.write_into_raw_buffer(0, 1, 3, &mut buffer);
+    assert_eq!(&buffer[..3], &[0xff; 3]);
+    assert_eq!(&buffer[7..], &[0xff; 3]);
+    assert_eq!(GrayVal32F::load_from_raw_buffer(0, 1, 3, &buffer), pixel);
+
+    let value = GrayVal::new(ScalarVal(0.1f64));
+    value.write_into_raw_buffer(0, 0, 1, &mut buffer[1..]);
+    assert_eq!(GrayVal::<f64>::load_from_raw_buffer(0, 0, 1, &buffer[1..]), value);
+}
+
+#[test]
+fn test_rgb_raw_buffer() {
+    use ScalarVal;
+
+    // two pixels per row with two bytes of padding
+    let mut buffer = [0u8; 16];
+    let mut pixel = RgbVal8U::new(ScalarVal(1), ScalarVal(2), ScalarVal(3));
+    pixel.write_into_raw_buffer(1, 1, 8, &mut buffer);
+    assert_eq!(buffer, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 0, 0]);
+    assert_eq!(RgbVal8U::load_from_raw_buffer(1, 1, 8, &buffer), pixel);
+    assert_eq!(Rgb8U::calc_minimum_pitch(2, 2), 6);
+
+    pixel.set_r(ScalarVal(10));
+    pixel.set_g(ScalarVal(20));
+    pixel.set_b(ScalarVal(30));
+    assert_eq!((pixel.r(), pixel.g(), pixel.b()), (ScalarVal(10), ScalarVal(20), ScalarVal(30)));
+
+    let mut buffer = [0u8; 2 * 24];
+    let wide = RgbVal32F::new(ScalarVal(0.25), ScalarVal(-1.5), ScalarVal(3.0));
+    wide.write_into_raw_buffer(1, 0, 24, &mut buffer);
+    assert_eq!(RgbVal32F::load_from_raw_buffer(1, 0, 24, &buffer), wide);
+    let deep = RgbVal16U::new(ScalarVal(1), ScalarVal(300), ScalarVal(65535));
+    deep.write_into_raw_buffer(0, 1, 24, &mut buffer);
+    assert_eq!(RgbVal16U::load_from_raw_buffer(0, 1, 24, &buffer), deep);
+    assert_eq!(RgbVal32F::load_from_raw_buffer(1, 0, 24, &buffer), wide);
+}
+
+#[test]
+fn test_rgba_raw_buffer() {
+    use ScalarVal;
+
+    assert_eq!(Rgba16U::calc_size_in_bytes(2, 3, 20), Some(60));
+    assert_eq!(Rgba16U::calc_size_in_bytes(2, 3, 15), None);
+
+    let mut buffer = [0u8; 60];
+    let pixel = RgbaVal16U::new(ScalarVal(1), ScalarVal(256), ScalarVal(4000), ScalarVal(65535));
+    pixel.write_into_raw_buffer(1, 2, 20, &mut buffer);
+    assert!(buffer[..48].iter().all(|&b| b == 0));
+    assert!(buffer[56..].iter().all(|&b| b == 0));
+    let loaded = RgbaVal16U::load_from_raw_buffer(1, 2, 20, &buffer);
+    assert_eq!(loaded, pixel);
+
+    let mut copy = [0u8; 60];
+    loaded.write_into_raw_buffer(1, 2, 20, &mut copy);
+    assert_eq!(&copy[..], &buffer[..]);
+}
+
+#[test]
+fn test_rgba_alpha_blend() {
+    use ScalarVal;
+
+    let red = RgbaVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(0), ScalarVal(255));
+    let blue = RgbaVal8U::new(ScalarVal(0), ScalarVal(0), ScalarVal(255), ScalarVal(255));
+    let clear = RgbaVal8U::new(ScalarVal(0), ScalarVal(0), ScalarVal(0), ScalarVal(0));
+    assert_eq!(red.alpha_blend(blue, 1.0), red);
+    assert_eq!(red.alpha_blend(blue, 0.0), blue);
+    assert_eq!(red.alpha_blend(clear, 0.5),
+               RgbaVal8U::new(ScalarVal(255), ScalarVal(0), ScalarVal(0), ScalarVal(128)));
+    assert_eq!(clear.alpha_blend(clear, 1.0), clear);
+
+    let half = red.alpha_blend(blue, 0.5);
+    assert_eq!((half.r(), half.b(), half.a()), (ScalarVal(128), ScalarVal(128), ScalarVal(255)));
+}
+
+#[test]
+fn test_channels_raw_buffer() {
+    use ScalarVal;
+
+    let mut buffer = [0u8; 12];
+    let single = ChannelsVal::<u16, 1>::new([ScalarVal(0xbeef)]);
+    single.write_into_raw_buffer(1, 1, 6, &mut buffer);
+    assert_eq!(ChannelsVal::<u16, 1>::load_from_raw_buffer(1, 1, 6, &buffer), single);
+    assert_eq!(Channels::<u16, 1>::calc_minimum_pitch(3, 1), 6);
+    assert_eq!(single.channel(0), ScalarVal(0xbeef));
+
+    let mut buffer = [0u8; 16];
+    let mut triple = ChannelsVal::<u8, 3>::new([ScalarVal(1), ScalarVal(2), ScalarVal(3)]);
+    triple.write_into_raw_buffer(1, 1, 8, &mut buffer);
+    assert_eq!(buffer, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 0, 0]);
+    assert_eq!(ChannelsVal::<u8, 3>::load_from_raw_buffer(1, 1, 8, &buffer), triple);
+    assert_eq!(Channels::<u8, 3>::calc_minimum_pitch(2, 1), 6);
+
+    triple.set_channel(2, ScalarVal(30));
+    let sum = triple + ChannelsVal::new([ScalarVal(10), ScalarVal(20), ScalarVal(30)]);
+    assert_eq!((sum.channel(0), sum.channel(1), sum.channel(2)),
+               (ScalarVal(11), ScalarVal(22), ScalarVal(60)));
+}
+
+#[test]
+#[should_panic(expected = "Invalid channel index (3) for 3 channels")]
+fn test_channels_out_of_range() {
+    use ScalarVal;
+
+    ChannelsVal::<u8, 3>::new([ScalarVal(1), ScalarVal(2), ScalarVal(3)]).channel(3);
+}
+
+#[test]
+fn test_palette_raw_buffer() {
+    let mut buffer = [0u8, 7, 0, 0, 0, 0];
+    let mut pixel = PaletteVal::load_from_raw_buffer(1, 0, 3, &buffer);
+    assert_eq!(pixel.index(), 7);
+
+    pixel.set_index(200);
+    pixel.write_into_raw_buffer(2, 1, 3, &mut buffer);
+    assert_eq!(buffer, [0, 7, 0, 0, 0, 200]);
+    assert_eq!(Palette::calc_size_in_bytes(3, 2, 2), None);
+}
+
+#[test]
+fn test_default_endianness() {
+    use {Endianness, Gray8U, Gray16U, Rgba16U, Rgb32F, Complex64F, SaturatingGray32U, Channels,
+         Binary};
+
+    // a pixel type which only implements the native byte order
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Pair {
+        a: u16,
+        b: u16,
+    }
+
+    impl Pixel for Pair {
+        const SCALAR_SIZE: usize = 2;
+
+        fn calc_minimum_pitch(width: u32, _height: u32) -> usize {
+            width as usize * 4
+        }
+        fn calc_size_in_bytes(width: u32, height: u32, pitch: u32) -> Option<usize> {
+            if pitch as usize >= Self::calc_minimum_pitch(width, height) {
+                Some(height as usize * pitch as usize)
+            } else {
+                None
+            }
+        }
+        fn load_from_raw_buffer(x: u32, y: u32, pitch: u32, buffer: &[u8]) -> Self {
+            let start = (y * pitch + x * 4) as usize;
+            Pair {
+                a: u16::from_ne_bytes([buffer[start], buffer[start + 1]]),
+                b: u16::from_ne_bytes([buffer[start + 2], buffer[start + 3]]),
+            }
+        }
+        fn write_into_raw_buffer(&self, x: u32, y: u32, pitch: u32, buffer: &mut [u8]) {
+            let start = (y * pitch + x * 4) as usize;
+            buffer[start..start + 2].copy_from_slice(&self.a.to_ne_bytes());
+            buffer[start + 2..start + 4].copy_from_slice(&self.b.to_ne_bytes());
+        }
+    }
+
+    let buffer = [0u8, 0, 0, 0, 9, 1, 2, 3, 4, 9];
+    let pixel = Pair::load_from_raw_buffer_with_endianness(0, 1, 5, &buffer, Endianness::Big);
+    assert_eq!(pixel, Pair { a: 0x0102, b: 0x0304 });
+    let pixel = Pair::load_from_raw_buffer_with_endianness(0, 1, 5, &buffer, Endianness::Little);
+    assert_eq!(pixel, Pair { a: 0x0201, b: 0x0403 });
+    assert_eq!(Pair::load_from_raw_buffer_with_endianness(0, 1, 5, &buffer, Endianness::Native),
+               Pair::load_from_raw_buffer(0, 1, 5, &buffer));
+
+    let mut buffer = [9u8; 10];
+    pixel.write_into_raw_buffer_with_endianness(0, 1, 5, &mut buffer, Endianness::Big);
+    assert_eq!(buffer, [9, 9, 9, 9, 9, 2, 1, 4, 3, 9]);
+    pixel.write_into_raw_buffer_with_endianness(0, 0, 5, &mut buffer, Endianness::Little);
+    assert_eq!(buffer, [1, 2, 3, 4, 9, 2, 1, 4, 3, 9]);
+
+    assert_eq!(Gray8U::SCALAR_SIZE, 1);
+    assert_eq!(Gray16U::SCALAR_SIZE, 2);
+    assert_eq!(Rgba16U::SCALAR_SIZE, 2);
+    assert_eq!(Rgb32F::SCALAR_SIZE, 4);
+    assert_eq!(Complex64F::SCALAR_SIZE, 8);
+    assert_eq!(SaturatingGray32U::SCALAR_SIZE, 4);
+    assert_eq!(Channels::<u16, 5>::SCALAR_SIZE, 2);
+    assert_eq!(Binary::SCALAR_SIZE, 1);
+}
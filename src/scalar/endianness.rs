@@ -0,0 +1,21 @@
+/// Byte order used when scalars are serialized into raw buffers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+    /// Byte order of the host, this is what the in-memory image buffers use.
+    Native,
+}
+
+impl Endianness {
+    /// Returns whether this is the byte order of the host.
+    pub(crate) fn is_native(self) -> bool {
+        match self {
+            Endianness::Little => cfg!(target_endian = "little"),
+            Endianness::Big => cfg!(target_endian = "big"),
+            Endianness::Native => true,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use Endianness;
 use std::ops::{Add, AddAssign};
 use std::ops::{Sub, SubAssign};
 use std::ops::{Mul, MulAssign};
@@ -20,8 +21,71 @@ use std::ops::{Div, DivAssign};
 /// ```
 pub trait Scalar:
     Copy + Clone + Debug
-    + PartialEq<Self>
+    + PartialEq<Self> + PartialOrd<Self>
     + Add<Self, Output = Self> + AddAssign<Self>
     + Sub<Self, Output = Self> + SubAssign<Self>
     + Mul<Self, Output = Self> + MulAssign<Self>
-    + Div<Self, Output = Self> + DivAssign<Self> {}
+    + Div<Self, Output = Self> + DivAssign<Self> {
+    /// Lower end of the value range used for intensities.
+    ///
+    /// For integer types this is the smallest representable value, for floating point types
+    /// it is `0.0`.
+    fn range_min() -> Self;
+    /// Upper end of the value range used for intensities.
+    ///
+    /// For integer types this is the largest representable value, for floating point types
+    /// it is `1.0`.
+    fn range_max() -> Self;
+    /// Converts the value into a `f64`.
+    ///
+    /// This is used by operations which need a common intermediate representation, like
+    /// filters which accumulate weighted sums.
+    fn to_f64(self) -> f64;
+    /// Converts a `f64` into this type.
+    ///
+    /// Integer types round to the nearest value and saturate at the bounds of the type.
+    fn from_f64(value: f64) -> Self;
+    /// Decodes a value from its byte representation in the given byte order.
+    ///
+    /// `bytes` does not need to be aligned.
+    ///
+    /// # Panics
+    /// If `bytes` is not exactly `size_of::<Self>()` long.
+    fn from_bytes(bytes: &[u8], endianness: Endianness) -> Self;
+    /// Writes the byte representation of the value in the given byte order into `bytes`.
+    ///
+    /// This is the inverse of [`from_bytes`](#tymethod.from_bytes), `bytes` does not need to
+    /// be aligned.
+    ///
+    /// # Panics
+    /// If `bytes` is not exactly `size_of::<Self>()` long.
+    fn write_bytes(self, bytes: &mut [u8], endianness: Endianness);
+    /// Addition which saturates at the bounds of integer types instead of overflowing.
+    ///
+    /// Floating point types use the regular addition.
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Subtraction which saturates at the bounds of integer types instead of overflowing.
+    ///
+    /// Floating point types use the regular subtraction.
+    fn saturating_sub(self, rhs: Self) -> Self;
+    /// Multiplication which saturates at the bounds of integer types instead of overflowing.
+    ///
+    /// Floating point types use the regular multiplication.
+    fn saturating_mul(self, rhs: Self) -> Self;
+    /// Addition which returns `None` instead of overflowing.
+    ///
+    /// For floating point types the result is `None` if it is not finite.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Subtraction which returns `None` instead of overflowing.
+    ///
+    /// For floating point types the result is `None` if it is not finite.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    /// Multiplication which returns `None` instead of overflowing.
+    ///
+    /// For floating point types the result is `None` if it is not finite.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Division which returns `None` for a zero divisor instead of panicking.
+    ///
+    /// For floating point types the result is `None` if it is not finite.
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+}
@@ -1,15 +1,133 @@
-use scalar::Scalar;
+use scalar::{Scalar, Endianness};
 use std::ops::{Add, AddAssign};
 use std::ops::{Sub, SubAssign};
 use std::ops::{Mul, MulAssign};
 use std::ops::{Div, DivAssign};
 
+macro_rules! impl_scalar_for_int {
+    ($scalar_type:ty) => (
+        impl Scalar for $scalar_type {
+            fn range_min() -> Self {
+                <$scalar_type>::min_value()
+            }
+            fn range_max() -> Self {
+                <$scalar_type>::max_value()
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn from_f64(value: f64) -> Self {
+                value.round() as $scalar_type
+            }
+            fn from_bytes(bytes: &[u8], endianness: Endianness) -> Self {
+                let mut raw = [0u8; ::std::mem::size_of::<$scalar_type>()];
+                raw.copy_from_slice(bytes);
+                match endianness {
+                    Endianness::Little => <$scalar_type>::from_le_bytes(raw),
+                    Endianness::Big => <$scalar_type>::from_be_bytes(raw),
+                    Endianness::Native => <$scalar_type>::from_ne_bytes(raw),
+                }
+            }
+            fn write_bytes(self, bytes: &mut [u8], endianness: Endianness) {
+                let raw = match endianness {
+                    Endianness::Little => self.to_le_bytes(),
+                    Endianness::Big => self.to_be_bytes(),
+                    Endianness::Native => self.to_ne_bytes(),
+                };
+                bytes.copy_from_slice(&raw);
+            }
+            fn saturating_add(self, rhs: Self) -> Self {
+                <$scalar_type>::saturating_add(self, rhs)
+            }
+            fn saturating_sub(self, rhs: Self) -> Self {
+                <$scalar_type>::saturating_sub(self, rhs)
+            }
+            fn saturating_mul(self, rhs: Self) -> Self {
+                <$scalar_type>::saturating_mul(self, rhs)
+            }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$scalar_type>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$scalar_type>::checked_sub(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$scalar_type>::checked_mul(self, rhs)
+            }
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                <$scalar_type>::checked_div(self, rhs)
+            }
+        }
+    )
+}
+impl_scalar_for_int!(u8);
+impl_scalar_for_int!(u16);
+impl_scalar_for_int!(u32);
+impl_scalar_for_int!(u64);
+impl_scalar_for_int!(i8);
+impl_scalar_for_int!(i16);
+impl_scalar_for_int!(i32);
+impl_scalar_for_int!(i64);
+impl_scalar_for_int!(i128);
 
-impl Scalar for u8 {}
-impl Scalar for u16 {}
-impl Scalar for u32 {}
-impl Scalar for f32 {}
-impl Scalar for f64 {}
+macro_rules! impl_scalar_for_float {
+    ($scalar_type:ty) => (
+        impl Scalar for $scalar_type {
+            fn range_min() -> Self {
+                0.0
+            }
+            fn range_max() -> Self {
+                1.0
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn from_f64(value: f64) -> Self {
+                value as $scalar_type
+            }
+            fn from_bytes(bytes: &[u8], endianness: Endianness) -> Self {
+                let mut raw = [0u8; ::std::mem::size_of::<$scalar_type>()];
+                raw.copy_from_slice(bytes);
+                match endianness {
+                    Endianness::Little => <$scalar_type>::from_le_bytes(raw),
+                    Endianness::Big => <$scalar_type>::from_be_bytes(raw),
+                    Endianness::Native => <$scalar_type>::from_ne_bytes(raw),
+                }
+            }
+            fn write_bytes(self, bytes: &mut [u8], endianness: Endianness) {
+                let raw = match endianness {
+                    Endianness::Little => self.to_le_bytes(),
+                    Endianness::Big => self.to_be_bytes(),
+                    Endianness::Native => self.to_ne_bytes(),
+                };
+                bytes.copy_from_slice(&raw);
+            }
+            fn saturating_add(self, rhs: Self) -> Self {
+                self + rhs
+            }
+            fn saturating_sub(self, rhs: Self) -> Self {
+                self - rhs
+            }
+            fn saturating_mul(self, rhs: Self) -> Self {
+                self * rhs
+            }
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                Some(self + rhs).filter(|value| value.is_finite())
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                Some(self - rhs).filter(|value| value.is_finite())
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                Some(self * rhs).filter(|value| value.is_finite())
+            }
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                Some(self / rhs).filter(|value| value.is_finite())
+            }
+        }
+    )
+}
+impl_scalar_for_float!(f32);
+impl_scalar_for_float!(f64);
 
 /// Newtype which wraps [`Scalar`](trait.Scalar.html)
 ///
@@ -29,9 +147,50 @@ impl Scalar for f64 {}
 /// let c = a * b;
 /// assert_eq!(c, ScalarVal(42));
 /// ```
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 pub struct ScalarVal<T>(pub T) where T: Scalar;
 
+impl<T> ScalarVal<T>
+    where T: Scalar
+{
+    /// Restricts the value to the interval [`min`, `max`].
+    ///
+    /// # Examples
+    /// ```
+    /// use img::ScalarVal;
+    /// assert_eq!(ScalarVal(300u16).clamp(ScalarVal(0), ScalarVal(255)), ScalarVal(255));
+    /// ```
+    ///
+    /// # Panics
+    /// If `min` is greater than `max`.
+    pub fn clamp(self, min: ScalarVal<T>, max: ScalarVal<T>) -> ScalarVal<T> {
+        assert!(min.0 <= max.0, "Invalid clamp range [{:?}, {:?}]", min.0, max.0);
+        if self.0 < min.0 {
+            min
+        } else if self.0 > max.0 {
+            max
+        } else {
+            self
+        }
+    }
+
+    /// Returns the smaller of both values.
+    ///
+    /// If the values are not comparable, i.e. one of them is a floating point NaN, `self` is
+    /// returned. So a NaN is only propagated if it is the receiver.
+    pub fn min(self, other: ScalarVal<T>) -> ScalarVal<T> {
+        if other.0 < self.0 { other } else { self }
+    }
+
+    /// Returns the larger of both values.
+    ///
+    /// If the values are not comparable, i.e. one of them is a floating point NaN, `self` is
+    /// returned. So a NaN is only propagated if it is the receiver.
+    pub fn max(self, other: ScalarVal<T>) -> ScalarVal<T> {
+        if other.0 > self.0 { other } else { self }
+    }
+}
+
 // derive ops
 macro_rules! derive_std_op_for_sc {
     ($self_type:ident, $inner_trait:ident, $op_type:ident, $op_fun:ident) => (
@@ -1,17 +1,52 @@
 mod generic;
 mod impl_core;
+mod endianness;
 
 pub use self::generic::*;
 pub use self::impl_core::*;
+pub use self::endianness::*;
 
 #[test]
 fn test_arithmetic() {
     let a = ScalarVal(1.0);
     let b = ScalarVal(1.0);
-    a + b;
+    let _ = a + b;
 
     let mut d = a;
     d += a;
 
-    a == b;
-}
\ No newline at end of file
+    let _ = a == b;
+}
+
+#[test]
+fn test_clamp() {
+    let (min, max) = (ScalarVal(10u8), ScalarVal(200u8));
+    assert_eq!(ScalarVal(3u8).clamp(min, max), ScalarVal(10));
+    assert_eq!(ScalarVal(42u8).clamp(min, max), ScalarVal(42));
+    assert_eq!(ScalarVal(255u8).clamp(min, max), ScalarVal(200));
+
+    let (min, max) = (ScalarVal(0.0f32), ScalarVal(1.0f32));
+    assert_eq!(ScalarVal(-0.5f32).clamp(min, max), ScalarVal(0.0));
+    assert_eq!(ScalarVal(0.25f32).clamp(min, max), ScalarVal(0.25));
+    assert_eq!(ScalarVal(1.5f32).clamp(min, max), ScalarVal(1.0));
+}
+
+#[test]
+#[should_panic]
+fn test_clamp_invalid_range() {
+    ScalarVal(1u8).clamp(ScalarVal(2), ScalarVal(1));
+}
+
+#[test]
+fn test_ordering() {
+    assert_eq!(ScalarVal(3u8).max(ScalarVal(7)), ScalarVal(7));
+    assert_eq!(ScalarVal(3u8).min(ScalarVal(7)), ScalarVal(3));
+    assert!(ScalarVal(3u8) < ScalarVal(7));
+    assert!(ScalarVal(-1.5f32) <= ScalarVal(-1.5));
+
+    let nan = ScalarVal(f64::NAN);
+    assert_eq!(ScalarVal(1.0).max(nan), ScalarVal(1.0));
+    assert_eq!(ScalarVal(1.0).min(nan), ScalarVal(1.0));
+    assert!(nan.max(ScalarVal(1.0)).0.is_nan());
+    assert_eq!(nan.partial_cmp(&ScalarVal(1.0)), None);
+}